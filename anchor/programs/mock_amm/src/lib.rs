@@ -0,0 +1,212 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
+};
+
+// Minimal constant-product (x * y = k) AMM. Only used on localnet as a swap router for the Testudo tests.
+
+declare_id!("2L7ytsy9TyQWPtfotsu2xjpXfKpasaFu1StdMdHtpR6y");
+
+#[program]
+pub mod mock_amm {
+    use super::*;
+
+    pub fn init_pool(ctx: Context<InitPool>, fee_bps: u16) -> Result<()> {
+        require_gte!(10000, fee_bps, MockAmmError::InvalidFee);
+
+        let pool = &mut ctx.accounts.pool;
+        pool.mint_a = ctx.accounts.mint_a.key();
+        pool.mint_b = ctx.accounts.mint_b.key();
+        pool.fee_bps = fee_bps;
+        pool.bump = ctx.bumps.pool;
+        Ok(())
+    }
+
+    pub fn add_liquidity(ctx: Context<AddLiquidity>, amount_a: u64, amount_b: u64) -> Result<()> {
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program_a.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.provider_ata_a.to_account_info(),
+                    to: ctx.accounts.vault_a.to_account_info(),
+                    mint: ctx.accounts.mint_a.to_account_info(),
+                    authority: ctx.accounts.provider.to_account_info(),
+                },
+            ),
+            amount_a,
+            ctx.accounts.mint_a.decimals,
+        )?;
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program_b.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.provider_ata_b.to_account_info(),
+                    to: ctx.accounts.vault_b.to_account_info(),
+                    mint: ctx.accounts.mint_b.to_account_info(),
+                    authority: ctx.accounts.provider.to_account_info(),
+                },
+            ),
+            amount_b,
+            ctx.accounts.mint_b.decimals,
+        )?;
+        Ok(())
+    }
+
+    // Swap either direction. The input vault is whichever of the pool's vaults holds the source mint.
+    pub fn swap(ctx: Context<Swap>, amount_in: u64, min_amount_out: u64) -> Result<()> {
+        let reserve_in = ctx.accounts.source_vault.amount as u128;
+        let reserve_out = ctx.accounts.destination_vault.amount as u128;
+        let fee_bps = ctx.accounts.pool.fee_bps as u128;
+
+        let amount_in_after_fee = (amount_in as u128) * (10000 - fee_bps) / 10000;
+        let amount_out = reserve_out
+            .checked_mul(amount_in_after_fee)
+            .and_then(|n| n.checked_div(reserve_in + amount_in_after_fee))
+            .ok_or(MockAmmError::MathOverflow)? as u64;
+        require_gt!(amount_out, 0, MockAmmError::InsufficientLiquidity);
+        require_gte!(amount_out, min_amount_out, MockAmmError::SlippageExceeded);
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.source_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.user_source.to_account_info(),
+                    to: ctx.accounts.source_vault.to_account_info(),
+                    mint: ctx.accounts.source_mint.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            ),
+            amount_in,
+            ctx.accounts.source_mint.decimals,
+        )?;
+
+        let pool = &ctx.accounts.pool;
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"pool",
+            pool.mint_a.as_ref(),
+            pool.mint_b.as_ref(),
+            &[pool.bump],
+        ]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.destination_token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.destination_vault.to_account_info(),
+                    to: ctx.accounts.user_destination.to_account_info(),
+                    mint: ctx.accounts.destination_mint.to_account_info(),
+                    authority: ctx.accounts.pool.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount_out,
+            ctx.accounts.destination_mint.decimals,
+        )?;
+
+        msg!("Mock AMM swap: {} in, {} out", amount_in, amount_out);
+        Ok(())
+    }
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Pool {
+    pub mint_a: Pubkey,
+    pub mint_b: Pubkey,
+    pub fee_bps: u16, // where 10000 = 100%
+    pub bump: u8,
+}
+
+#[derive(Accounts)]
+pub struct InitPool<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Pool::INIT_SPACE,
+        seeds = [b"pool", mint_a.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+    )]
+    pub pool: Account<'info, Pool>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint_a,
+        token::authority = pool,
+        token::token_program = token_program_a,
+        seeds = [b"vault", pool.key().as_ref(), mint_a.key().as_ref()],
+        bump,
+    )]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init,
+        payer = payer,
+        token::mint = mint_b,
+        token::authority = pool,
+        token::token_program = token_program_b,
+        seeds = [b"vault", pool.key().as_ref(), mint_b.key().as_ref()],
+        bump,
+    )]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    pub provider: Signer<'info>,
+    #[account(has_one = mint_a, has_one = mint_b)]
+    pub pool: Account<'info, Pool>,
+    pub mint_a: InterfaceAccount<'info, Mint>,
+    pub mint_b: InterfaceAccount<'info, Mint>,
+    #[account(mut, seeds = [b"vault", pool.key().as_ref(), mint_a.key().as_ref()], bump)]
+    pub vault_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [b"vault", pool.key().as_ref(), mint_b.key().as_ref()], bump)]
+    pub vault_b: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint_a)]
+    pub provider_ata_a: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = mint_b)]
+    pub provider_ata_b: InterfaceAccount<'info, TokenAccount>,
+    pub token_program_a: Interface<'info, TokenInterface>,
+    pub token_program_b: Interface<'info, TokenInterface>,
+}
+
+#[derive(Accounts)]
+pub struct Swap<'info> {
+    pub user: Signer<'info>,
+    #[account(
+        constraint = (pool.mint_a == source_mint.key() && pool.mint_b == destination_mint.key())
+            || (pool.mint_b == source_mint.key() && pool.mint_a == destination_mint.key())
+            @MockAmmError::InvalidPoolMints
+    )]
+    pub pool: Account<'info, Pool>,
+    pub source_mint: InterfaceAccount<'info, Mint>,
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut, token::mint = source_mint, token::authority = user)]
+    pub user_source: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = destination_mint)]
+    pub user_destination: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [b"vault", pool.key().as_ref(), source_mint.key().as_ref()], bump)]
+    pub source_vault: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, seeds = [b"vault", pool.key().as_ref(), destination_mint.key().as_ref()], bump)]
+    pub destination_vault: InterfaceAccount<'info, TokenAccount>,
+    pub source_token_program: Interface<'info, TokenInterface>,
+    pub destination_token_program: Interface<'info, TokenInterface>,
+}
+
+#[error_code]
+pub enum MockAmmError {
+    #[msg("Fee must be at most 10000 bps")]
+    InvalidFee,
+    #[msg("Pool does not trade these mints")]
+    InvalidPoolMints,
+    #[msg("Not enough liquidity for this swap")]
+    InsufficientLiquidity,
+    #[msg("Output below minimum")]
+    SlippageExceeded,
+    #[msg("Math overflow")]
+    MathOverflow,
+}
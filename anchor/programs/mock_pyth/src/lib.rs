@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+// Writable Pyth price accounts. Only used on localnet as the oracle for the Testudo tests.
+// Price accounts are created by the client and owned by this program. `set_price` writes the Pyth v2
+// layout (240 bytes), `set_price_update` a fully verified pull oracle PriceUpdateV2 (133 bytes).
+// Loaded at the Pyth receiver's address, since Testudo only accepts PriceUpdateV2 accounts it owns.
+
+declare_id!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+const PRICE_ACCOUNT_LEN: usize = 240;
+const PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+const PRICE_UPDATE_LEN: usize = 133;
+
+#[program]
+pub mod mock_pyth {
+    use super::*;
+
+    pub fn set_price(
+        ctx: Context<SetPrice>,
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: i64,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.price_account.try_borrow_mut_data()?;
+        require_gte!(
+            data.len(),
+            PRICE_ACCOUNT_LEN,
+            MockPythError::AccountTooSmall
+        );
+
+        data[0..4].copy_from_slice(&PYTH_MAGIC.to_le_bytes());
+        data[4..8].copy_from_slice(&PYTH_VERSION.to_le_bytes());
+        data[8..12].copy_from_slice(&PYTH_ACCOUNT_TYPE_PRICE.to_le_bytes());
+        data[20..24].copy_from_slice(&expo.to_le_bytes());
+        data[96..104].copy_from_slice(&publish_time.to_le_bytes());
+        data[208..216].copy_from_slice(&price.to_le_bytes());
+        data[216..224].copy_from_slice(&conf.to_le_bytes());
+        data[224..228].copy_from_slice(&PYTH_STATUS_TRADING.to_le_bytes());
+        Ok(())
+    }
+
+    pub fn set_price_update(
+        ctx: Context<SetPrice>,
+        feed_id: [u8; 32],
+        price: i64,
+        conf: u64,
+        expo: i32,
+        publish_time: i64,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.price_account.try_borrow_mut_data()?;
+        require_gte!(data.len(), PRICE_UPDATE_LEN, MockPythError::AccountTooSmall);
+
+        // Discriminator, write authority (left zeroed) and verification level
+        data[0..8].copy_from_slice(&PRICE_UPDATE_DISCRIMINATOR);
+        data[40] = VERIFICATION_LEVEL_FULL;
+        // Price message, then the posted slot
+        data[41..73].copy_from_slice(&feed_id);
+        data[73..81].copy_from_slice(&price.to_le_bytes());
+        data[81..89].copy_from_slice(&conf.to_le_bytes());
+        data[89..93].copy_from_slice(&expo.to_le_bytes());
+        data[93..101].copy_from_slice(&publish_time.to_le_bytes());
+        data[101..109].copy_from_slice(&publish_time.to_le_bytes());
+        data[109..117].copy_from_slice(&price.to_le_bytes());
+        data[117..125].copy_from_slice(&conf.to_le_bytes());
+        data[125..133].copy_from_slice(&Clock::get()?.slot.to_le_bytes());
+        Ok(())
+    }
+}
+
+#[derive(Accounts)]
+pub struct SetPrice<'info> {
+    pub authority: Signer<'info>,
+
+    #[account(mut, owner = crate::ID)]
+    /// CHECK: Raw Pyth-format price account owned by this program
+    pub price_account: UncheckedAccount<'info>,
+}
+
+#[error_code]
+pub enum MockPythError {
+    #[msg("Price account is too small for this layout")]
+    AccountTooSmall,
+}
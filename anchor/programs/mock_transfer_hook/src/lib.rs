@@ -0,0 +1,98 @@
+use anchor_lang::prelude::*;
+use spl_discriminator::SplDiscriminate;
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+// Transfer hook counting the transfers of each mint. Only used on localnet to test Token-2022 hooked mints.
+// The counter PDA is the hook's single extra account, resolved by Token-2022 from the validation account.
+
+declare_id!("7xUvVkpFaPWwrz1ShHEebqS4XYWXz1zBZwEuVrPrpmcP");
+
+#[program]
+pub mod mock_transfer_hook {
+    use super::*;
+
+    pub fn initialize_extra_account_meta_list(
+        ctx: Context<InitializeExtraAccountMetaList>,
+    ) -> Result<()> {
+        let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+        ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &extra_account_metas()?)?;
+        Ok(())
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn transfer_hook(ctx: Context<TransferHook>, _amount: u64) -> Result<()> {
+        ctx.accounts.counter.transfers += 1;
+        Ok(())
+    }
+}
+
+// Counter PDA seeded by the mint, which is account index 1 of the Execute instruction
+fn extra_account_metas() -> Result<Vec<ExtraAccountMeta>> {
+    Ok(vec![ExtraAccountMeta::new_with_seeds(
+        &[
+            Seed::Literal {
+                bytes: b"counter".to_vec(),
+            },
+            Seed::AccountKey { index: 1 },
+        ],
+        false,
+        true,
+    )?])
+}
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetaList<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = ExtraAccountMetaList::size_of(1)?,
+        seeds = [b"extra-account-metas", mint.key().as_ref()],
+        bump,
+    )]
+    /// CHECK: TLV account holding the extra account metas, written above
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+
+    /// CHECK: Mint whose transfers run this hook
+    pub mint: UncheckedAccount<'info>,
+
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + TransferCounter::INIT_SPACE,
+        seeds = [b"counter", mint.key().as_ref()],
+        bump,
+    )]
+    pub counter: Account<'info, TransferCounter>,
+
+    pub system_program: Program<'info, System>,
+}
+
+// Account order fixed by the transfer hook interface
+#[derive(Accounts)]
+pub struct TransferHook<'info> {
+    /// CHECK: Source token account
+    pub source_token: UncheckedAccount<'info>,
+    /// CHECK: Mint being transferred
+    pub mint: UncheckedAccount<'info>,
+    /// CHECK: Destination token account
+    pub destination_token: UncheckedAccount<'info>,
+    /// CHECK: Source owner or delegate
+    pub owner: UncheckedAccount<'info>,
+    #[account(seeds = [b"extra-account-metas", mint.key().as_ref()], bump)]
+    /// CHECK: Validation account read by Token-2022
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    #[account(mut, seeds = [b"counter", mint.key().as_ref()], bump)]
+    pub counter: Account<'info, TransferCounter>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct TransferCounter {
+    pub transfers: u64,
+}
@@ -0,0 +1,219 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    burn, mint_to, transfer_checked, Burn, Mint, MintTo, TokenAccount, TokenInterface,
+    TransferChecked,
+};
+
+// Share-based vault implementing the Testudo yield adapter interface. Only used on localnet.
+// Deposits mint position tokens pro rata to the reserve, so anything sent to the reserve is yield for holders.
+
+declare_id!("FJPbiKPk5Xk6nyruTXU3sf2JFNCiiytBAAdD9ba4tdZf");
+
+#[program]
+pub mod mock_yield_vault {
+    use super::*;
+
+    pub fn init_vault(ctx: Context<InitVault>) -> Result<()> {
+        let vault = &mut ctx.accounts.vault;
+        vault.underlying_mint = ctx.accounts.underlying_mint.key();
+        vault.position_mint = ctx.accounts.position_mint.key();
+        vault.reserve = ctx.accounts.reserve.key();
+        vault.bump = ctx.bumps.vault;
+        Ok(())
+    }
+
+    // Interface: sha256("yield_adapter:deposit")[..8]
+    #[instruction(discriminator = [163, 150, 39, 75, 39, 90, 123, 189])]
+    pub fn deposit(ctx: Context<AdapterAccounts>, amount: u64) -> Result<()> {
+        let shares = shares_for(
+            amount,
+            ctx.accounts.position_mint.supply,
+            ctx.accounts.reserve.amount,
+        )?;
+        require_gt!(shares, 0, MockYieldVaultError::InvalidAmount);
+
+        transfer_checked(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.owner_underlying.to_account_info(),
+                    to: ctx.accounts.reserve.to_account_info(),
+                    mint: ctx.accounts.underlying_mint.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            amount,
+            ctx.accounts.underlying_mint.decimals,
+        )?;
+
+        let underlying_mint_key = ctx.accounts.underlying_mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            underlying_mint_key.as_ref(),
+            &[ctx.accounts.vault.bump],
+        ]];
+        mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.position_mint.to_account_info(),
+                    to: ctx.accounts.owner_position.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            shares,
+        )
+    }
+
+    // Interface: sha256("yield_adapter:withdraw")[..8]
+    #[instruction(discriminator = [171, 156, 210, 254, 15, 187, 94, 86])]
+    pub fn withdraw(ctx: Context<AdapterAccounts>, shares: u64) -> Result<()> {
+        let amount = value_of(
+            shares,
+            ctx.accounts.position_mint.supply,
+            ctx.accounts.reserve.amount,
+        )?;
+
+        burn(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Burn {
+                    mint: ctx.accounts.position_mint.to_account_info(),
+                    from: ctx.accounts.owner_position.to_account_info(),
+                    authority: ctx.accounts.owner.to_account_info(),
+                },
+            ),
+            shares,
+        )?;
+
+        let underlying_mint_key = ctx.accounts.underlying_mint.key();
+        let signer_seeds: &[&[&[u8]]] = &[&[
+            b"vault",
+            underlying_mint_key.as_ref(),
+            &[ctx.accounts.vault.bump],
+        ]];
+        transfer_checked(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                TransferChecked {
+                    from: ctx.accounts.reserve.to_account_info(),
+                    to: ctx.accounts.owner_underlying.to_account_info(),
+                    mint: ctx.accounts.underlying_mint.to_account_info(),
+                    authority: ctx.accounts.vault.to_account_info(),
+                },
+                signer_seeds,
+            ),
+            amount,
+            ctx.accounts.underlying_mint.decimals,
+        )
+    }
+
+    // Interface: sha256("yield_adapter:position_value")[..8]. Returns the underlying amount the shares redeem for
+    #[instruction(discriminator = [120, 246, 202, 237, 237, 51, 120, 18])]
+    pub fn position_value(ctx: Context<PositionValue>, shares: u64) -> Result<u64> {
+        value_of(
+            shares,
+            ctx.accounts.position_mint.supply,
+            ctx.accounts.reserve.amount,
+        )
+    }
+}
+
+fn shares_for(amount: u64, supply: u64, reserve: u64) -> Result<u64> {
+    if supply == 0 || reserve == 0 {
+        return Ok(amount);
+    }
+    Ok((amount as u128 * supply as u128 / reserve as u128) as u64)
+}
+
+fn value_of(shares: u64, supply: u64, reserve: u64) -> Result<u64> {
+    require_gte!(supply, shares, MockYieldVaultError::InvalidAmount);
+    if supply == 0 {
+        return Ok(0);
+    }
+    Ok((shares as u128 * reserve as u128 / supply as u128) as u64)
+}
+
+#[derive(Accounts)]
+pub struct InitVault<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = 8 + Vault::INIT_SPACE,
+        seeds = [b"vault", underlying_mint.key().as_ref()],
+        bump,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"position", vault.key().as_ref()],
+        bump,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = vault,
+        mint::token_program = token_program,
+    )]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init,
+        payer = payer,
+        seeds = [b"reserve", vault.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = vault,
+        token::token_program = token_program,
+    )]
+    pub reserve: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+// Account order fixed by the yield adapter interface for deposit and withdraw
+#[derive(Accounts)]
+pub struct AdapterAccounts<'info> {
+    #[account(
+        has_one = underlying_mint,
+        has_one = position_mint,
+        has_one = reserve,
+    )]
+    pub vault: Account<'info, Vault>,
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub reserve: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = underlying_mint)]
+    pub owner_underlying: InterfaceAccount<'info, TokenAccount>,
+    #[account(mut, token::mint = position_mint)]
+    pub owner_position: InterfaceAccount<'info, TokenAccount>,
+    pub owner: Signer<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+// Account order fixed by the yield adapter interface for position_value
+#[derive(Accounts)]
+pub struct PositionValue<'info> {
+    #[account(has_one = position_mint, has_one = reserve)]
+    pub vault: Account<'info, Vault>,
+    pub reserve: InterfaceAccount<'info, TokenAccount>,
+    pub position_mint: InterfaceAccount<'info, Mint>,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct Vault {
+    pub underlying_mint: Pubkey,
+    pub position_mint: Pubkey,
+    pub reserve: Pubkey,
+    pub bump: u8,
+}
+
+#[error_code]
+pub enum MockYieldVaultError {
+    #[msg("Amount too small or above the position supply")]
+    InvalidAmount,
+}
@@ -0,0 +1,82 @@
+use anchor_lang::prelude::*;
+
+// Wrapped SOL mint. Native SOL held by a Centurion is priced and configured through this mint's whitelist entry.
+pub const WSOL_MINT: Pubkey = pubkey!("So11111111111111111111111111111111111111112");
+
+// Fee percentages are stored in basis points, where 10000 = 100%
+pub const FEE_PERCENT_DENOMINATOR: u16 = 10000;
+
+// Current layout version of the Legate. Accounts on an older layout must go through migrate_legate
+pub const LEGATE_VERSION: u8 = 1;
+
+// Current layout version of the Centurion. Accounts on an older layout must go through migrate_centurion
+pub const CENTURION_VERSION: u8 = 1;
+
+// Decimals of native SOL (lamports per SOL = 10^9)
+pub const SOL_DECIMALS: u8 = 9;
+
+// Oracle prices are compared in micro-USD (6 decimals)
+pub const USD_DECIMALS: u8 = 6;
+
+// Pyth receiver program, the owner of every pull oracle PriceUpdateV2 account
+pub const PYTH_RECEIVER_PROGRAM_ID: Pubkey = pubkey!("rec5EKMGg6MxZYaMdyBfgwp4d5rB9T1VQH5pJv5LtFJ");
+
+// Maximum number of fee tiers the Legate can hold
+pub const MAX_FEE_TIERS: usize = 5;
+
+// Jupiter aggregator, the swap router approved at Legate initialization
+pub const JUPITER_PROGRAM_ID: Pubkey = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4");
+
+// Maximum number of swap routers the Legate can approve
+pub const MAX_SWAP_ROUTERS: usize = 5;
+
+// Programs the Centurion signer may be lent to during a swap, approved at Legate initialization
+pub const COMPUTE_BUDGET_PROGRAM_ID: Pubkey =
+    pubkey!("ComputeBudget111111111111111111111111111111");
+
+// Maximum number of programs the Legate can approve as CPI targets
+pub const MAX_APPROVED_CPI_PROGRAMS: usize = 10;
+
+// Token program instructions the Centurion may sign for during a swap (SOL wrap and unwrap only)
+pub const TOKEN_INSTRUCTION_CLOSE_ACCOUNT: u8 = 9;
+pub const TOKEN_INSTRUCTION_SYNC_NATIVE: u8 = 17;
+pub const ALLOWED_TOKEN_INSTRUCTIONS: [u8; 2] = [
+    TOKEN_INSTRUCTION_CLOSE_ACCOUNT,
+    TOKEN_INSTRUCTION_SYNC_NATIVE,
+];
+
+// Maximum number of testudos a rebalance policy can weight
+pub const MAX_REBALANCE_TARGETS: usize = 8;
+
+// Accounts passed per policy target to `rebalance`: testudo, mint, price account, treasury ATA, token program
+pub const REBALANCE_ACCOUNTS_PER_TARGET: usize = 5;
+
+// Risk flags recorded on a whitelist entry from the mint's authorities and Token-2022 extensions
+pub const MINT_RISK_FREEZE_AUTHORITY: u8 = 1 << 0; // The issuer can freeze testudo accounts
+pub const MINT_RISK_TRANSFER_FEE: u8 = 1 << 1; // Transfers withhold a fee
+pub const MINT_RISK_TRANSFER_HOOK: u8 = 1 << 2; // Transfers run the entry's approved hook program
+
+// Metaplex Token Metadata program, whose verified collections NFT mode accepts
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey =
+    pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Maximum number of NFT collections the Legate can approve
+pub const MAX_NFT_COLLECTIONS: usize = 10;
+
+// Maximum number of NFT or semi-fungible mints a Centurion can vault, apart from its testudos
+pub const MAX_NFTS_PER_CENTURION: usize = 20;
+
+// Stake config account, still expected by the stake program's DelegateStake instruction
+pub const STAKE_CONFIG_ID: Pubkey = pubkey!("StakeConfig11111111111111111111111111111111");
+
+// Maximum number of native stake accounts a Centurion can hold
+pub const MAX_STAKE_ACCOUNTS_PER_CENTURION: usize = 5;
+
+// Maximum number of yield adapter programs the Legate can approve
+pub const MAX_YIELD_PROGRAMS: usize = 5;
+
+// Maximum number of open yield positions per Centurion
+pub const MAX_YIELD_POSITIONS_PER_CENTURION: usize = 5;
+
+// Maximum length in bytes of the memo attached to a third-party deposit
+pub const MAX_DEPOSIT_MEMO_LEN: usize = 64;
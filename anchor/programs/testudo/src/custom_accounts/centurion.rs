@@ -1,3 +1,5 @@
+use crate::constants::CENTURION_VERSION;
+use crate::errors::ErrorCode::MaxTestudosReached;
 use anchor_lang::prelude::*;
 
 // Centurion account acts as umbrella account for all testudo accounts. It stores the pubkey to the user's 'password' private key.
@@ -15,6 +17,16 @@ pub struct Centurion {
     pub lamport_balance: u64,         // Balance of SOL in the centurion account
     #[max_len(30)]
     pub testudos: Vec<TestudoData>, // List of testudo account data
+    // Fields below were added after the first deployment. New fields are only ever appended, and existing
+    // accounts are brought up to date with migrate_centurion
+    pub version: u8, // Layout version, CENTURION_VERSION once initialized or migrated
+    #[max_len(20)]
+    pub nfts: Vec<Pubkey>, // Mints of the vaulted NFTs, which don't count toward the testudo limit
+    #[max_len(5)]
+    pub stake_accounts: Vec<Pubkey>, // Native stake accounts with the Centurion as staker and withdrawer
+    #[max_len(5)]
+    pub yield_positions: Vec<YieldPosition>, // Open positions in Legate-approved yield programs
+    pub accepts_third_party_deposits: bool, // Whether anyone, not just the owner, can deposit into this Centurion
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug)]
@@ -22,3 +34,60 @@ pub struct TestudoData {
     pub token_mint: Pubkey,
     pub testudo_pubkey: Pubkey,
 }
+
+// A position in a yield adapter vault. The position tokens sit in a Centurion-owned account
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, InitSpace, Debug, PartialEq, Eq)]
+pub struct YieldPosition {
+    pub adapter_program: Pubkey,
+    pub vault: Pubkey,
+    pub underlying_mint: Pubkey,
+    pub position_mint: Pubkey,
+}
+
+impl Centurion {
+    // Whether the account is on the current layout. Older ones must go through migrate_centurion first
+    pub fn is_migrated(&self) -> bool {
+        self.version == CENTURION_VERSION
+    }
+
+    // Record a new testudo, respecting the Legate's per-user testudo limit
+    pub fn register_testudo(
+        &mut self,
+        token_mint: Pubkey,
+        testudo_pubkey: Pubkey,
+        max_testudos_per_user: u16,
+    ) -> Result<()> {
+        require_gt!(
+            max_testudos_per_user,
+            self.testudos.len() as u16,
+            MaxTestudosReached
+        );
+        self.testudos.push(TestudoData {
+            token_mint,
+            testudo_pubkey,
+        });
+        Ok(())
+    }
+
+    // Whether any open yield position holds this mint, which must be unwound before it is swept
+    pub fn has_yield_position_in(&self, underlying_mint: &Pubkey) -> bool {
+        self.yield_positions
+            .iter()
+            .any(|position| position.underlying_mint == *underlying_mint)
+    }
+}
+
+// Centurion layout as first deployed, before the account was versioned. Only read by migrate_centurion
+#[derive(AnchorDeserialize, InitSpace)]
+pub struct LegacyCenturion {
+    pub authority: Pubkey,
+    pub backup_owner: Option<Pubkey>,
+    pub pubkey_to_password: Pubkey,
+    pub bump: u8,
+    pub is_initialized: bool,
+    pub created_at: u64,
+    pub last_accessed: u64,
+    pub lamport_balance: u64,
+    #[max_len(30)]
+    pub testudos: Vec<TestudoData>,
+}
@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+// A stop-loss or take-profit order. Approved once by the user with their password, then executed by
+// any keeper once the source mint's oracle price crosses the trigger. The keeper tip is prefunded here.
+
+#[account]
+#[derive(InitSpace)]
+pub struct ConditionalOrder {
+    pub authority: Pubkey,        // Owner of the Centurion that placed the order
+    pub centurion: Pubkey,        // Centurion whose testudos are traded
+    pub order_id: u64,            // Lets a Centurion hold several orders at once
+    pub bump: u8,                 // Bump seed used in PDA derivation
+    pub source_mint: Pubkey,      // Mint sold when triggered. Its registered price feed is watched
+    pub destination_mint: Pubkey, // Mint bought when triggered
+    pub amount_in: u64,           // Exact source amount sold, in base units
+    pub min_amount_out: u64,      // Minimum destination amount, net of the protocol fee
+    pub trigger: OrderTrigger,    // Which way the price must cross
+    pub trigger_price: u64,       // In micro-USD (USD_DECIMALS)
+    pub keeper_tip_lamports: u64, // Paid to the keeper that executes the order
+    pub created_at: u64,          // Timestamp of order creation
+    pub max_slippage_bps: u16, // Largest loss of oracle value allowed against the two price feeds
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub enum OrderTrigger {
+    PriceBelow, // Stop-loss: sell once the price is at or below the trigger
+    PriceAbove, // Take-profit: sell once the price is at or above the trigger
+}
+
+impl OrderTrigger {
+    pub fn is_reached(&self, price: u64, trigger_price: u64) -> bool {
+        match self {
+            OrderTrigger::PriceBelow => price <= trigger_price,
+            OrderTrigger::PriceAbove => price >= trigger_price,
+        }
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+// A dollar-cost-averaging order. Approved once by the user with their password, then executed one
+// cycle at a time by any keeper, who is paid a tip from lamports prefunded into this account.
+
+#[account]
+#[derive(InitSpace)]
+pub struct DcaOrder {
+    pub authority: Pubkey,        // Owner of the Centurion that placed the order
+    pub centurion: Pubkey,        // Centurion whose testudos are traded
+    pub order_id: u64,            // Lets a Centurion hold several orders at once
+    pub bump: u8,                 // Bump seed used in PDA derivation
+    pub source_mint: Pubkey,      // Mint sold each cycle (wSOL sells the Centurion's SOL)
+    pub destination_mint: Pubkey, // Mint bought each cycle (wSOL buys SOL into the Centurion)
+    pub amount_per_cycle: u64,    // Exact source amount sold each cycle, in base units
+    pub min_amount_out_per_cycle: u64, // Minimum destination amount each cycle, net of the protocol fee
+    pub interval_seconds: u64,         // Time between cycles
+    pub total_cycles: u16,             // Number of cycles to run
+    pub cycles_executed: u16,          // Number of cycles run so far
+    pub next_execution_at: u64,        // Timestamp from which the next cycle may run
+    pub keeper_tip_lamports: u64,      // Paid to the keeper for each executed cycle
+    pub created_at: u64,               // Timestamp of order creation
+    pub max_slippage_bps: Option<u16>, // If set, each cycle must also return the oracle value of its input, less this
+}
@@ -1,3 +1,4 @@
+use crate::constants::{COMPUTE_BUDGET_PROGRAM_ID, LEGATE_VERSION, WSOL_MINT};
 use anchor_lang::prelude::*;
 
 // Legate account is the admin account for the entire program.
@@ -16,6 +17,26 @@ pub struct Legate {
     pub percent_for_fees: u16,       // where 10000 = 100%
     #[max_len(50)]
     pub testudo_token_whitelist: Vec<TestudoTokenWhitelist>, // List of token mints that can be used with testudos
+    // Fields below were added after the first deployment. New fields are only ever appended, and existing
+    // accounts are brought up to date with migrate_legate
+    pub version: u8, // Layout version, LEGATE_VERSION once initialized or migrated
+    pub fee_tier_mode: FeeTierMode, // How the fee tiers are applied to a withdrawal
+    #[max_len(5)]
+    pub fee_tiers: Vec<FeeTier>, // Amount brackets with their own fee rate, sorted by ascending threshold
+    pub min_fee_lamports: u64, // Minimum fee charged on SOL (and wSOL) withdrawals
+    pub flat_fee_lamports: u64, // Flat fee added to every SOL (and wSOL) withdrawal
+    pub round_fees_up: bool,   // Round percentage fees up instead of down
+    pub operation_fees: OperationFees, // Fee setting for each type of operation
+    #[max_len(10)]
+    pub approved_cpi_programs: Vec<Pubkey>, // Programs that may be invoked with the Centurion signer during a swap
+    #[max_len(5)]
+    pub approved_swap_routers: Vec<Pubkey>, // Router programs a swap may be routed through (Jupiter, AMMs, ...)
+    pub max_price_staleness_seconds: u64, // Oldest oracle price the program will act on
+    pub max_price_confidence_bps: u16, // Widest oracle confidence interval accepted, relative to the price
+    #[max_len(10)]
+    pub nft_collections: Vec<Pubkey>, // Verified collections whose NFTs can be vaulted
+    #[max_len(5)]
+    pub yield_programs: Vec<Pubkey>, // Yield adapter programs testudo balances may be deployed into
 }
 
 // Data structure for the testudo token whitelist info
@@ -27,4 +48,180 @@ pub struct TestudoTokenWhitelist {
     #[max_len(10)]
     pub token_symbol: String,
     pub token_decimals: u8,
+    pub percent_for_fees: Option<u16>, // Per-mint fee override where 10000 = 100%, applied instead of the tiers. None falls back to the Legate's rate and tiers
+    pub min_fee: u64, // Minimum fee in base units. Ignored for wSOL, which uses the Legate's lamport minimum
+    pub flat_fee: u64, // Flat fee in base units added to every withdrawal. Ignored for wSOL
+    pub price_feed: Option<PriceFeed>, // Oracle account quoting this mint in USD
+    pub transfer_hook_program: Option<Pubkey>, // Transfer hook program approved for this mint, if it has one
+    pub risk_flags: u8, // MINT_RISK_* flags, set by the program from the mint when whitelisted
+}
+
+// Oracle account registered for a mint, and the format it is parsed with
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub struct PriceFeed {
+    pub kind: OracleKind,
+    pub price_account: Pubkey,
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub enum OracleKind {
+    PythV2, // Pyth push oracle price account
+    // Pyth pull oracle PriceUpdateV2 account, fully verified. The update must carry this feed's price
+    PythPriceUpdate { feed_id: [u8; 32] },
+}
+
+// A fee bracket. Amounts at or above the threshold are charged this tier's rate
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub struct FeeTier {
+    pub threshold: u64, // In whole tokens (scaled by the mint's decimals when applied)
+    pub percent_for_fees: u16, // where 10000 = 100%
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub enum FeeTierMode {
+    Bracket,     // The whole amount is charged the rate of the highest tier it reaches
+    Progressive, // Each band of the amount is charged its own tier's rate, like tax bands
+}
+
+// Fee setting for a single type of operation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub struct OperationFee {
+    pub enabled: bool,                 // When false the operation is free
+    pub percent_for_fees: Option<u16>, // Flat rate for this operation where 10000 = 100%. None uses the mint and tier schedule
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, PartialEq, Eq, Debug)]
+pub struct OperationFees {
+    pub withdraw: OperationFee,          // withdraw_sol, withdraw_spl
+    pub backup_recovery: OperationFee,   // withdraw_to_backup, withdraw_sol_to_backup
+    pub close: OperationFee,             // close_testudo, close_centurion
+    pub swap: OperationFee,              // swap
+    pub internal_transfer: OperationFee, // wrap_sol, unwrap_sol
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum FeeOperation {
+    Withdraw,
+    BackupRecovery,
+    Close,
+    Swap,
+    InternalTransfer,
+}
+
+impl OperationFees {
+    // Withdrawals, backup recovery and closes use the standard schedule. Swaps and internal transfers are free
+    pub fn initial() -> Self {
+        let standard_fee = OperationFee {
+            enabled: true,
+            percent_for_fees: None,
+        };
+        let free = OperationFee {
+            enabled: false,
+            percent_for_fees: None,
+        };
+        OperationFees {
+            withdraw: standard_fee,
+            backup_recovery: standard_fee,
+            close: standard_fee,
+            swap: free,
+            internal_transfer: free,
+        }
+    }
+
+    pub fn get(&self, operation: FeeOperation) -> &OperationFee {
+        match operation {
+            FeeOperation::Withdraw => &self.withdraw,
+            FeeOperation::BackupRecovery => &self.backup_recovery,
+            FeeOperation::Close => &self.close,
+            FeeOperation::Swap => &self.swap,
+            FeeOperation::InternalTransfer => &self.internal_transfer,
+        }
+    }
+
+    pub fn get_mut(&mut self, operation: FeeOperation) -> &mut OperationFee {
+        match operation {
+            FeeOperation::Withdraw => &mut self.withdraw,
+            FeeOperation::BackupRecovery => &mut self.backup_recovery,
+            FeeOperation::Close => &mut self.close,
+            FeeOperation::Swap => &mut self.swap,
+            FeeOperation::InternalTransfer => &mut self.internal_transfer,
+        }
+    }
+}
+
+impl Legate {
+    // Whether the account is on the current layout. Older ones must go through migrate_legate first
+    pub fn is_migrated(&self) -> bool {
+        self.version == LEGATE_VERSION
+    }
+
+    // Programs the Centurion signer may be lent to during a swap, before the admin approves any others
+    pub fn initial_approved_cpi_programs() -> Vec<Pubkey> {
+        vec![
+            anchor_spl::token::ID,
+            anchor_spl::token_2022::ID,
+            anchor_spl::associated_token::ID,
+            anchor_lang::system_program::ID,
+            COMPUTE_BUDGET_PROGRAM_ID,
+        ]
+    }
+
+    // Fee rate override set on a mint's whitelist entry, if any. Native SOL uses the wSOL mint's entry.
+    pub fn fee_override_for_mint(&self, mint: &Pubkey) -> Option<u16> {
+        self.testudo_token_whitelist
+            .iter()
+            .find(|t| t.token_mint == *mint)
+            .and_then(|t| t.percent_for_fees)
+    }
+
+    // Minimum and flat fee for a mint in its base units. wSOL base units are lamports, so SOL and wSOL
+    // share the Legate's lamport settings.
+    pub fn fee_limits_for_mint(&self, mint: &Pubkey) -> (u64, u64) {
+        if *mint == WSOL_MINT {
+            return (self.min_fee_lamports, self.flat_fee_lamports);
+        }
+        self.testudo_token_whitelist
+            .iter()
+            .find(|t| t.token_mint == *mint)
+            .map_or((0, 0), |t| (t.min_fee, t.flat_fee))
+    }
+
+    // Price feed registered for a whitelisted mint, if any
+    pub fn price_feed_for_mint(&self, mint: &Pubkey) -> Option<PriceFeed> {
+        self.testudo_token_whitelist
+            .iter()
+            .find(|t| t.token_mint == *mint)
+            .and_then(|t| t.price_feed)
+    }
+
+    // Transfer hook program approved for a whitelisted mint, if any
+    pub fn transfer_hook_program_for_mint(&self, mint: &Pubkey) -> Option<Pubkey> {
+        self.testudo_token_whitelist
+            .iter()
+            .find(|t| t.token_mint == *mint)
+            .and_then(|t| t.transfer_hook_program)
+    }
+}
+
+// Legate layout as first deployed, before the account was versioned. Only read by migrate_legate
+#[derive(AnchorDeserialize)]
+pub struct LegacyLegate {
+    pub authority: Pubkey,
+    pub bump: u8,
+    pub is_initialized: bool,
+    pub _last_updated: u64, // Replaced with the time of the migration
+    pub max_centurions_per_user: u8,
+    pub max_testudos_per_user: u16,
+    pub max_whitelisted_mints: u16,
+    pub treasury_acc: Pubkey,
+    pub percent_for_fees: u16,
+    pub testudo_token_whitelist: Vec<LegacyTestudoTokenWhitelist>,
+}
+
+#[derive(AnchorDeserialize)]
+pub struct LegacyTestudoTokenWhitelist {
+    pub token_mint: Pubkey,
+    pub token_name: String,
+    pub token_symbol: String,
+    pub token_decimals: u8,
 }
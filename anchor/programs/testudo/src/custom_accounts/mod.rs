@@ -3,3 +3,9 @@ pub mod legate;
 pub mod centurion;
 
 pub mod testudo;
+
+pub mod dca_order;
+
+pub mod conditional_order;
+
+pub mod rebalance_policy;
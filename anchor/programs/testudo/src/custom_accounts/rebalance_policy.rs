@@ -0,0 +1,26 @@
+use crate::constants::MAX_REBALANCE_TARGETS;
+use anchor_lang::prelude::*;
+
+// Target weights for a Centurion's testudos. Set by the user with their password; rebalances are then run
+// by the user or by any keeper once the portfolio has drifted far enough from the targets.
+
+#[account]
+#[derive(InitSpace)]
+pub struct RebalancePolicy {
+    pub authority: Pubkey, // Owner of the Centurion
+    pub centurion: Pubkey, // Centurion whose testudos are rebalanced
+    pub bump: u8,          // Bump seed used in PDA derivation
+    #[max_len(MAX_REBALANCE_TARGETS)]
+    pub targets: Vec<RebalanceTarget>, // Target weights, summing to 10000 bps
+    pub drift_threshold_bps: u16, // Keepers may only rebalance once a testudo is this far from its target weight
+    pub max_slippage_bps: u16, // Largest loss of oracle value allowed across all swaps of one rebalance
+    pub min_interval_seconds: u64, // Minimum time between keeper rebalances
+    pub last_rebalanced_at: u64, // Timestamp of the last rebalance
+    pub created_at: u64,       // Timestamp of policy creation
+}
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, InitSpace, Debug, PartialEq, Eq)]
+pub struct RebalanceTarget {
+    pub mint: Pubkey,    // Mint of a registered testudo with a price feed
+    pub weight_bps: u16, // Share of the portfolio's USD value
+}
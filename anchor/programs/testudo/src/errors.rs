@@ -46,4 +46,138 @@ pub enum ErrorCode {
     CenturionNotEmptyOfSplTokens,
     #[msg("Invalid remaining accounts given")]
     InvalidRemainingAccounts,
+    #[msg("Fee percent cannot exceed 10000 (100%)")]
+    InvalidFeePercent,
+    #[msg("Account is already on the current layout")]
+    AccountAlreadyMigrated,
+    #[msg("Legate must be migrated to the current layout with migrate_legate")]
+    LegateNotMigrated,
+    #[msg("Too many fee tiers")]
+    TooManyFeeTiers,
+    #[msg("Fee tier thresholds must be strictly ascending")]
+    FeeTiersNotAscending,
+    #[msg("Swap output is below the minimum amount out")]
+    SlippageExceeded,
+    #[msg("Swap spent more than the maximum amount in")]
+    MaxAmountInExceeded,
+    #[msg("A Centurion token account lost funds during the swap")]
+    CenturionTokenAccountDrained,
+    #[msg("Program is not approved for invocation with the Centurion signer")]
+    UnapprovedCpiProgram,
+    #[msg("Token program instruction is not approved for invocation with the Centurion signer")]
+    UnapprovedTokenInstruction,
+    #[msg("Program already approved")]
+    CpiProgramAlreadyApproved,
+    #[msg("Max approved programs reached")]
+    MaxApprovedCpiProgramsReached,
+    #[msg("Swap router not approved by the Legate")]
+    UnapprovedSwapRouter,
+    #[msg("Swap router already approved")]
+    SwapRouterAlreadyApproved,
+    #[msg("Max swap routers reached")]
+    MaxSwapRoutersReached,
+    #[msg("Invalid DCA order parameters")]
+    InvalidDcaOrder,
+    #[msg("Order source and destination testudos must exist first")]
+    OrderTestudoNotRegistered,
+    #[msg("DCA cycle is not due yet")]
+    DcaCycleNotDue,
+    #[msg("DCA order has no cycles left")]
+    DcaOrderCompleted,
+    #[msg("DCA cycle must sell exactly the order's amount per cycle")]
+    DcaCycleAmountMismatch,
+    #[msg("Mint has no price feed registered")]
+    MissingPriceFeed,
+    #[msg("Price account does not match the mint's registered price feed")]
+    InvalidPriceFeed,
+    #[msg("Account is not a valid price account")]
+    InvalidPriceAccount,
+    #[msg("Oracle price is not currently available")]
+    PriceUnavailable,
+    #[msg("Oracle price is stale")]
+    StalePrice,
+    #[msg("Oracle price confidence interval too wide")]
+    PriceConfidenceTooWide,
+    #[msg("Invalid conditional order parameters")]
+    InvalidConditionalOrder,
+    #[msg("Order trigger price has not been reached")]
+    TriggerNotReached,
+    #[msg("Order must sell exactly its amount in")]
+    OrderAmountMismatch,
+    #[msg("Invalid rebalance policy")]
+    InvalidRebalancePolicy,
+    #[msg("Invalid rebalance accounts or legs")]
+    InvalidRebalanceLeg,
+    #[msg("Portfolio has not drifted past the rebalance threshold")]
+    RebalanceNotNeeded,
+    #[msg("Rebalance interval has not elapsed")]
+    RebalanceTooSoon,
+    #[msg("Rebalance lost more value than its slippage budget")]
+    SlippageBudgetExceeded,
+    #[msg("Mint has a permanent delegate")]
+    MintHasPermanentDelegate,
+    #[msg("Mint is non-transferable")]
+    MintNonTransferable,
+    #[msg("Mint creates token accounts frozen by default")]
+    MintDefaultFrozen,
+    #[msg("Mint transfer hook program is not the approved one")]
+    UnexpectedTransferHook,
+    #[msg("Mint has a testudo; use the withdraw instructions")]
+    RegisteredTestudoMint,
+    #[msg("NFT collection already approved")]
+    NftCollectionAlreadyApproved,
+    #[msg("Max NFT collections reached")]
+    MaxNftCollectionsReached,
+    #[msg("NFT is not in a verified collection approved by the Legate")]
+    NftCollectionNotApproved,
+    #[msg("Invalid NFT metadata account")]
+    InvalidNftMetadata,
+    #[msg("NFT mints must have 0 decimals")]
+    InvalidNftMint,
+    #[msg("Programmable NFTs are not supported")]
+    UnsupportedNftStandard,
+    #[msg("Max vaulted NFTs reached")]
+    MaxNftsReached,
+    #[msg("NFT is not vaulted in this Centurion")]
+    NftNotVaulted,
+    #[msg("NFT amount must be above zero")]
+    InvalidNftAmount,
+    #[msg("Centurion must be migrated to the current layout with migrate_centurion")]
+    CenturionNotMigrated,
+    #[msg("Mint is a vaulted NFT; use the NFT withdraw instructions")]
+    VaultedNftMint,
+    #[msg("Max stake accounts reached")]
+    MaxStakeAccountsReached,
+    #[msg("Stake account is not held by this Centurion")]
+    StakeAccountNotTracked,
+    #[msg("Stake amount must be above zero")]
+    InvalidStakeAmount,
+    #[msg("Centurion still holds stake accounts")]
+    CenturionHasStakeAccounts,
+    #[msg("Yield program already approved")]
+    YieldProgramAlreadyApproved,
+    #[msg("Max yield programs reached")]
+    MaxYieldProgramsReached,
+    #[msg("Yield program not approved by the Legate")]
+    UnapprovedYieldProgram,
+    #[msg("Max yield positions reached")]
+    MaxYieldPositionsReached,
+    #[msg("Yield position not found")]
+    YieldPositionNotFound,
+    #[msg("Accounts do not match the recorded yield position")]
+    YieldPositionMismatch,
+    #[msg("Yield adapter did not move the expected balances")]
+    YieldAdapterMisbehaved,
+    #[msg("Yield positions in this mint must be exited first")]
+    YieldPositionsOpen,
+    #[msg("Yield amount must be above zero")]
+    InvalidYieldAmount,
+    #[msg("Yield adapter returned no position value")]
+    MissingYieldReturnData,
+    #[msg("Mint is an open yield position; use the yield position instructions")]
+    YieldPositionMint,
+    #[msg("Centurion does not accept third-party deposits")]
+    ThirdPartyDepositsDisabled,
+    #[msg("Deposit memo too long")]
+    DepositMemoTooLong,
 }
@@ -0,0 +1,165 @@
+use anchor_lang::prelude::*;
+
+// Events emitted by the program so off-chain indexers can follow activity without parsing logs.
+
+#[event]
+pub struct SwapExecuted {
+    pub centurion: Pubkey,
+    pub source_mint: Pubkey,
+    pub destination_mint: Pubkey,
+    pub amount_in: u64,  // Measured decrease of the source testudo
+    pub amount_out: u64, // Measured increase of the destination testudo, before the protocol fee
+    pub fee: u64,        // Protocol fee sent to the treasury, in the destination mint
+}
+
+#[event]
+pub struct DcaCycleExecuted {
+    pub dca_order: Pubkey,
+    pub centurion: Pubkey,
+    pub keeper: Pubkey,
+    pub cycle: u16, // 1-based number of the cycle just executed
+    pub amount_in: u64,
+    pub amount_out: u64, // Before the protocol fee
+    pub fee: u64,
+    pub keeper_tip: u64, // Lamports paid to the keeper
+}
+
+#[event]
+pub struct ConditionalOrderExecuted {
+    pub conditional_order: Pubkey,
+    pub centurion: Pubkey,
+    pub keeper: Pubkey,
+    pub oracle_price: u64, // Source mint price in micro-USD when the order ran
+    pub amount_in: u64,
+    pub amount_out: u64, // Before the protocol fee
+    pub fee: u64,
+    pub keeper_tip: u64, // Lamports paid to the keeper
+}
+
+#[event]
+pub struct RebalanceExecuted {
+    pub rebalance_policy: Pubkey,
+    pub centurion: Pubkey,
+    pub executor: Pubkey,   // Owner or keeper that ran the rebalance
+    pub legs: u8,           // Number of swaps run
+    pub max_drift_bps: u16, // Largest distance from a target weight before the rebalance
+    pub value_in_usd: u64,  // Oracle value sold, in micro-USD
+    pub value_out_usd: u64, // Oracle value bought before protocol fees, in micro-USD
+}
+
+#[event]
+pub struct SplDeposited {
+    pub centurion: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,          // Sent by the depositor
+    pub amount_received: u64, // Credited to the testudo, net of the mint's transfer fee
+}
+
+#[event]
+pub struct SplWithdrawn {
+    pub centurion: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey,    // Token account paid out to
+    pub amount: u64,          // Debited from the testudo, protocol fee included
+    pub protocol_fee: u64,    // Sent to the treasury
+    pub amount_received: u64, // Credited to the recipient, net of the mint's transfer fee
+}
+
+#[event]
+pub struct SolWrapped {
+    pub centurion: Pubkey,
+    pub amount: u64, // Lamports moved from `lamport_balance` into the wSOL testudo
+    pub protocol_fee: u64, // Taken from `lamport_balance` on top of `amount`, sent to the treasury
+}
+
+#[event]
+pub struct SolUnwrapped {
+    pub centurion: Pubkey,
+    pub amount: u64, // Lamports moved from the wSOL testudo into `lamport_balance`
+    pub protocol_fee: u64, // Taken back out of `lamport_balance`, sent to the treasury
+}
+
+#[event]
+pub struct CenturionReconciled {
+    pub centurion: Pubkey,
+    pub previous_balance: u64,   // lamport_balance before reconciling
+    pub reconciled_balance: u64, // Lamports above the rent-exempt minimum
+}
+
+#[event]
+pub struct TokensRescued {
+    pub centurion: Pubkey,
+    pub mint: Pubkey,
+    pub token_account: Pubkey, // Stray Centurion-owned account, closed by the rescue
+    pub amount: u64,           // Balance sent to the authority
+    pub amount_received: u64,  // Credited to the authority, net of the mint's transfer fee
+}
+
+#[event]
+pub struct NftDeposited {
+    pub centurion: Pubkey,
+    pub mint: Pubkey,
+    pub collection: Pubkey, // Verified collection the deposit was accepted for
+    pub amount: u64,        // 1 for an NFT, any amount for a semi-fungible token
+}
+
+#[event]
+pub struct NftWithdrawn {
+    pub centurion: Pubkey,
+    pub mint: Pubkey,
+    pub recipient: Pubkey, // Token account paid out to, the authority's or the backup's
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakeAccountCreated {
+    pub centurion: Pubkey,
+    pub stake_account: Pubkey,
+    pub amount: u64, // Lamports moved out of lamport_balance, on top of the stake account's rent
+}
+
+#[event]
+pub struct StakeWithdrawn {
+    pub centurion: Pubkey,
+    pub stake_account: Pubkey,
+    pub amount: u64,  // Lamports credited back to lamport_balance
+    pub closed: bool, // Whether the stake account was emptied and dropped from the Centurion
+}
+
+#[event]
+pub struct StakeMovedToBackup {
+    pub centurion: Pubkey,
+    pub stake_account: Pubkey,
+    pub backup_owner: Pubkey, // New staker and withdrawer
+}
+
+#[event]
+pub struct YieldPositionEntered {
+    pub centurion: Pubkey,
+    pub adapter_program: Pubkey,
+    pub vault: Pubkey,
+    pub underlying_mint: Pubkey,
+    pub amount: u64, // Underlying taken from the testudo
+    pub shares: u64, // Position tokens credited for it
+}
+
+#[event]
+pub struct YieldPositionExited {
+    pub centurion: Pubkey,
+    pub adapter_program: Pubkey,
+    pub vault: Pubkey,
+    pub underlying_mint: Pubkey,
+    pub shares: u64,          // Position tokens redeemed
+    pub amount_received: u64, // Underlying paid back into the testudo
+    pub closed: bool,         // Whether the whole position was exited
+}
+
+#[event]
+pub struct ThirdPartyDeposit {
+    pub centurion: Pubkey,
+    pub depositor: Pubkey,
+    pub mint: Pubkey,         // WSOL_MINT for native SOL credited to lamport_balance
+    pub amount: u64,          // Sent by the depositor
+    pub amount_received: u64, // Credited to the Centurion, net of the mint's transfer fee
+    pub memo: Option<String>, // Depositor's reference, for the owner to match the payment
+}
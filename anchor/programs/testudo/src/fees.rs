@@ -0,0 +1,80 @@
+use crate::constants::FEE_PERCENT_DENOMINATOR;
+use crate::custom_accounts::legate::{FeeOperation, FeeTierMode, Legate};
+use crate::errors::ErrorCode::ArithmeticOverflow;
+use anchor_lang::prelude::*;
+
+// Shared fee calculation for every path that charges a protocol fee.
+
+// Fee owed on `amount` (in base units) of `mint` for `operation`. Disabled operations are free. An
+// operation with its own rate bypasses the mint and tier schedule, and a mint with a whitelist override
+// pays that rate on the whole amount, so the tiers never undercut or overrule it. Otherwise the tiers
+// apply, with amounts below the first tier paying the Legate's global rate. The flat fee is added on top,
+// the minimum fee is applied after that, and the result never exceeds `amount` so balances can always
+// be emptied.
+pub fn calculate_fee(
+    legate: &Legate,
+    operation: FeeOperation,
+    mint: &Pubkey,
+    amount: u64,
+    decimals: u8,
+) -> Result<u64> {
+    let operation_fee = legate.operation_fees.get(operation);
+    if !operation_fee.enabled {
+        return Ok(0);
+    }
+
+    let base_percent = legate.percent_for_fees;
+    let amount = amount as u128;
+    let one_token = 10u128
+        .checked_pow(decimals as u32)
+        .ok_or(ArithmeticOverflow)?;
+
+    // Sum of (band size * band rate), divided once at the end to keep rounding loss to a single step
+    let fee_numerator: u128 = match (
+        operation_fee.percent_for_fees,
+        legate.fee_override_for_mint(mint),
+        legate.fee_tier_mode,
+    ) {
+        (Some(operation_percent), _, _) => amount * operation_percent as u128,
+        (None, Some(mint_percent), _) => amount * mint_percent as u128,
+        (None, None, FeeTierMode::Bracket) => {
+            let percent = legate
+                .fee_tiers
+                .iter()
+                .rev()
+                .find(|tier| (tier.threshold as u128).saturating_mul(one_token) <= amount)
+                .map_or(base_percent, |tier| tier.percent_for_fees);
+            amount * percent as u128
+        }
+        (None, None, FeeTierMode::Progressive) => {
+            let mut numerator: u128 = 0;
+            let mut band_start: u128 = 0;
+            let mut band_percent = base_percent;
+            for tier in legate.fee_tiers.iter() {
+                let tier_start = (tier.threshold as u128).saturating_mul(one_token);
+                if tier_start >= amount {
+                    break;
+                }
+                numerator += (tier_start - band_start) * band_percent as u128;
+                band_start = tier_start;
+                band_percent = tier.percent_for_fees;
+            }
+            numerator + (amount - band_start) * band_percent as u128
+        }
+    };
+
+    let denominator = FEE_PERCENT_DENOMINATOR as u128;
+    let percent_fee = if legate.round_fees_up {
+        fee_numerator.div_ceil(denominator)
+    } else {
+        fee_numerator / denominator
+    };
+
+    let (min_fee, flat_fee) = legate.fee_limits_for_mint(mint);
+    let fee = percent_fee
+        .saturating_add(flat_fee as u128)
+        .max(min_fee as u128)
+        .min(amount);
+
+    u64::try_from(fee).map_err(|_| ArithmeticOverflow.into())
+}
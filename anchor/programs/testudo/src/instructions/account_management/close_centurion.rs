@@ -1,8 +1,14 @@
-use crate::custom_accounts::{centurion::Centurion, legate::Legate};
+use crate::constants::{SOL_DECIMALS, WSOL_MINT};
+use crate::custom_accounts::{
+    centurion::Centurion,
+    legate::{FeeOperation, Legate},
+};
 use crate::errors::ErrorCode::{
-    CenturionNotEmptyOfSplTokens, CenturionNotInitialized, InvalidAuthority,
-    InvalidPasswordSignature, InvalidTreasuryAccount, LegateNotInitialized,
+    CenturionHasStakeAccounts, CenturionNotEmptyOfSplTokens, CenturionNotInitialized,
+    CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature, InvalidTreasuryAccount,
+    LegateNotInitialized, LegateNotMigrated, YieldPositionsOpen,
 };
+use crate::fees::calculate_fee;
 use anchor_lang::prelude::*;
 
 // TODO: IMPLEMENT FEE ON WITHDRAWING SOL DURING CLOSE OF ACCOUNT
@@ -25,8 +31,12 @@ pub struct CloseCenturion<'info> {
         seeds = [b"centurion".as_ref(), authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
-        constraint = centurion.testudos.is_empty() @CenturionNotEmptyOfSplTokens
+        constraint = centurion.testudos.is_empty() @CenturionNotEmptyOfSplTokens,
+        constraint = centurion.nfts.is_empty() @CenturionNotEmptyOfSplTokens,
+        constraint = centurion.stake_accounts.is_empty() @CenturionHasStakeAccounts,
+        constraint = centurion.yield_positions.is_empty() @YieldPositionsOpen
     )]
     centurion: Account<'info, Centurion>,
 
@@ -35,6 +45,7 @@ pub struct CloseCenturion<'info> {
         seeds = [b"legate"],
         bump = legate.bump,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 
@@ -54,11 +65,13 @@ pub fn process_close_centurion(ctx: Context<CloseCenturion>) -> Result<()> {
     let centurion_balance = ctx.accounts.centurion.to_account_info().lamports();
 
     // Calculate fee
-    let fee = centurion_balance
-        .checked_mul(ctx.accounts.legate.percent_for_fees as u64)
-        .unwrap_or(0)
-        .checked_div(10000)
-        .unwrap_or(0);
+    let fee = calculate_fee(
+        &ctx.accounts.legate,
+        FeeOperation::Close,
+        &WSOL_MINT,
+        centurion_balance,
+        SOL_DECIMALS,
+    )?;
 
     // Subtract the fee from the centurion's balance and add it to the treasury's balance
     ctx.accounts.centurion.sub_lamports(fee)?;
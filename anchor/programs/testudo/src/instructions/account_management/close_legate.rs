@@ -1,5 +1,5 @@
 use crate::custom_accounts::legate::Legate;
-use crate::errors::ErrorCode::{InvalidAuthority, LegateNotInitialized};
+use crate::errors::ErrorCode::{InvalidAuthority, LegateNotInitialized, LegateNotMigrated};
 
 use anchor_lang::prelude::*;
 
@@ -16,6 +16,7 @@ pub struct CloseLegate<'info> {
         seeds = [b"legate"],
         bump,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
         has_one = authority @InvalidAuthority
     )]
     pub legate: Account<'info, Legate>,
@@ -1,14 +1,20 @@
 use crate::custom_accounts::centurion::Centurion;
-use crate::custom_accounts::legate::Legate;
+use crate::custom_accounts::legate::{FeeOperation, Legate};
 use crate::errors::ErrorCode::{
-    ArithmeticOverflow, CenturionNotInitialized, InvalidATA, InvalidAuthority,
-    InvalidPasswordSignature, InvalidTokenMint, InvalidTreasuryAccount, LegateNotInitialized,
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InvalidATA,
+    InvalidAuthority, InvalidPasswordSignature, InvalidTokenMint, InvalidTreasuryAccount,
+    LegateNotInitialized, LegateNotMigrated, YieldPositionsOpen,
+};
+use crate::events::SplWithdrawn;
+use crate::fees::calculate_fee;
+use crate::transfers::{
+    amount_after_transfer_fee, harvest_withheld_fees, require_approved_transfer_hook,
+    transfer_tokens,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{
-    close_account, transfer_checked, CloseAccount, Mint, TokenAccount, TokenInterface,
-    TransferChecked,
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
 };
 
 // Delete a testudo account
@@ -41,6 +47,7 @@ pub struct CloseTestudo<'info> {
         seeds = [b"centurion".as_ref(), authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
@@ -50,6 +57,7 @@ pub struct CloseTestudo<'info> {
         seeds = [b"legate"],
         bump = legate.bump,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 
@@ -65,12 +73,15 @@ pub struct CloseTestudo<'info> {
         constraint = testudo.mint == mint.key() @InvalidTokenMint,
         // Ensure the ATA is for the correct Centurion (User)
         constraint = testudo.owner == centurion.key() @InvalidATA,
+        // Positions paid out into this testudo must be exited before it goes
+        constraint = !centurion.has_yield_position_in(&mint.key()) @YieldPositionsOpen,
     )]
     // Centurion ATA
     pub testudo: InterfaceAccount<'info, TokenAccount>,
 
-    // MINT
+    // MINT. Writable so withheld transfer fees can be harvested into it
     #[account(
+        mut,
         constraint = legate.testudo_token_whitelist.iter().any(|m| m.token_mint == mint.key()) @InvalidTokenMint,
     )]
     pub mint: InterfaceAccount<'info, Mint>,
@@ -103,7 +114,9 @@ pub struct CloseTestudo<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn process_close_testudo(ctx: Context<CloseTestudo>) -> Result<()> {
+pub fn process_close_testudo<'info>(
+    ctx: Context<'_, '_, '_, 'info, CloseTestudo<'info>>,
+) -> Result<()> {
     let centurion: &mut Account<'_, Centurion> = &mut ctx.accounts.centurion;
     let testudo_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.testudo;
     let authority: &Signer<'_> = &ctx.accounts.authority;
@@ -119,27 +132,23 @@ pub fn process_close_testudo(ctx: Context<CloseTestudo>) -> Result<()> {
         InvalidTokenMint
     );
 
+    // Hooked mints must still point at the hook program approved on their whitelist entry
+    require_approved_transfer_hook(&ctx.accounts.legate, &ctx.accounts.mint)?;
+
     let amount_in_decimals = testudo_ata.amount;
 
-    let withdraw_fee = amount_in_decimals
-        .checked_mul(ctx.accounts.legate.percent_for_fees as u64)
-        .unwrap_or(0)
-        .checked_div(10000)
-        .unwrap_or(0);
+    // The protocol fee is charged on what actually leaves, after the mint's transfer fee
+    let withdraw_fee = calculate_fee(
+        &ctx.accounts.legate,
+        FeeOperation::Close,
+        &ctx.accounts.mint.key(),
+        amount_after_transfer_fee(&ctx.accounts.mint, amount_in_decimals)?,
+        ctx.accounts.mint.decimals,
+    )?;
     let amount_after_fee = amount_in_decimals
         .checked_sub(withdraw_fee)
         .ok_or(ArithmeticOverflow)?;
 
-    // Get the testudo account for the token
-    let testudo_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.testudo;
-    // Get the user's ATA for the token
-    let authority_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.authority_ata;
-    // Get the treasury ATA for the token
-    let treasury_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.treasury_ata;
-
-    // Get the number of decimals for the token
-    let decimals: u8 = ctx.accounts.mint.decimals;
-
     let signer_seeds: &[&[&[u8]]] = &[&[
         b"centurion",
         ctx.accounts.authority.key.as_ref(),
@@ -147,45 +156,50 @@ pub fn process_close_testudo(ctx: Context<CloseTestudo>) -> Result<()> {
     ]];
 
     if withdraw_fee > 0 {
-        // Set up the CPI accounts for the transfer of fee
-        let cpi_accounts_for_fee = TransferChecked {
-            from: testudo_ata.to_account_info(),
-            to: treasury_ata.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            authority: centurion.to_account_info(),
-        };
-
-        // Set up the CPI context for the transfer of fee
-        let cpi_context_for_fee = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts_for_fee,
+        // Transfer the fee to the treasury
+        transfer_tokens(
+            token_program.to_account_info(),
+            testudo_ata.to_account_info(),
+            ctx.accounts.treasury_ata.to_account_info(),
+            centurion.to_account_info(),
+            &ctx.accounts.mint,
+            withdraw_fee,
             signer_seeds,
-        );
-
-        // Perform the transfer
-        transfer_checked(cpi_context_for_fee, withdraw_fee, decimals)?;
+            ctx.remaining_accounts,
+        )?;
     }
 
+    let mut amount_received = 0;
     if amount_after_fee > 0 {
-        // Set up the CPI accounts for the transfer of amount after fee
-        let cpi_accounts_for_withdraw = TransferChecked {
-            from: testudo_ata.to_account_info(),
-            to: authority_ata.to_account_info(),
-            mint: ctx.accounts.mint.to_account_info(),
-            authority: centurion.to_account_info(),
-        };
-
-        // Set up the CPI context for the transfer of amount after fee
-        let cpi_context_for_withdraw = CpiContext::new_with_signer(
-            ctx.accounts.token_program.to_account_info(),
-            cpi_accounts_for_withdraw,
+        // Transfer the amount after fee to the authority
+        amount_received = transfer_tokens(
+            token_program.to_account_info(),
+            testudo_ata.to_account_info(),
+            ctx.accounts.authority_ata.to_account_info(),
+            centurion.to_account_info(),
+            &ctx.accounts.mint,
+            amount_after_fee,
             signer_seeds,
-        );
-
-        // Perform the transfer
-        transfer_checked(cpi_context_for_withdraw, amount_after_fee, decimals)?;
+            ctx.remaining_accounts,
+        )?;
     }
 
+    emit!(SplWithdrawn {
+        centurion: centurion.key(),
+        mint: ctx.accounts.mint.key(),
+        recipient: ctx.accounts.authority_ata.key(),
+        amount: amount_in_decimals,
+        protocol_fee: withdraw_fee,
+        amount_received,
+    });
+
+    // Fees withheld from earlier incoming transfers would block the close
+    harvest_withheld_fees(
+        token_program.to_account_info(),
+        &ctx.accounts.mint,
+        testudo_ata.to_account_info(),
+    )?;
+
     // Close the ATA
     let cpi_accounts: CloseAccount<'_> = CloseAccount {
         account: testudo_ata.to_account_info(),
@@ -1,3 +1,4 @@
+use crate::constants::CENTURION_VERSION;
 use crate::custom_accounts::centurion::Centurion;
 use crate::errors::ErrorCode::AccountAlreadyInitialized;
 use anchor_lang::prelude::*;
@@ -50,5 +51,7 @@ pub fn process_init_centurion(
     centurion_data.last_accessed = current_time as u64;
     centurion_data.lamport_balance = 0;
     centurion_data.testudos = Vec::new();
+    centurion_data.version = CENTURION_VERSION;
+    centurion_data.accepts_third_party_deposits = false;
     Ok(())
 }
@@ -1,4 +1,5 @@
-use crate::custom_accounts::legate::Legate;
+use crate::constants::{JUPITER_PROGRAM_ID, LEGATE_VERSION};
+use crate::custom_accounts::legate::{FeeTierMode, Legate, OperationFees};
 use crate::errors::ErrorCode::AccountAlreadyInitialized;
 
 // Initialize the admin Legate account.
@@ -49,6 +50,23 @@ pub fn process_init_legate(ctx: Context<InitLegate>, treasury_acc: Pubkey) -> Re
 
     legate_data.treasury_acc = treasury_acc;
     legate_data.percent_for_fees = 15; // where 15 = 0.15%
+                                       // No fee tiers initially, so every withdrawal pays the flat rate
+    legate_data.fee_tier_mode = FeeTierMode::Bracket;
+    legate_data.fee_tiers = vec![];
+    // No minimum or flat fees initially. Fees round down until the admin opts into rounding up
+    legate_data.min_fee_lamports = 0;
+    legate_data.flat_fee_lamports = 0;
+    legate_data.round_fees_up = false;
+    // Withdrawals, backup recovery and closes use the standard schedule. Swaps and internal transfers are free
+    legate_data.operation_fees = OperationFees::initial();
+    // Programs the Centurion signer may be lent to during a swap
+    legate_data.approved_cpi_programs = Legate::initial_approved_cpi_programs();
+    // Oracle prices must be under a minute old and within 2% confidence
+    legate_data.max_price_staleness_seconds = 60;
+    legate_data.max_price_confidence_bps = 200;
+    // Routers a swap may be executed through
+    legate_data.approved_swap_routers = vec![JUPITER_PROGRAM_ID];
+    legate_data.version = LEGATE_VERSION;
     Ok(())
 }
 
@@ -1,8 +1,8 @@
-use crate::custom_accounts::centurion::{Centurion, TestudoData};
+use crate::custom_accounts::centurion::Centurion;
 use crate::custom_accounts::legate::Legate;
 use crate::errors::ErrorCode::{
-    CenturionNotInitialized, InvalidAuthority, LegateNotInitialized, MaxTestudosReached,
-    UnsupportedTokenMint,
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, LegateNotInitialized,
+    LegateNotMigrated, UnsupportedTokenMint,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -20,6 +20,7 @@ pub struct InitTestudo<'info> {
         seeds = [b"legate"],
         bump,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 
@@ -29,6 +30,7 @@ pub struct InitTestudo<'info> {
         seeds = [b"centurion", authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
@@ -62,19 +64,13 @@ pub struct InitTestudo<'info> {
 
 pub fn process_init_testudo(ctx: Context<InitTestudo>) -> Result<()> {
     let centurion_data: &mut Account<'_, Centurion> = &mut ctx.accounts.centurion;
-    let testudo_data: TestudoData = TestudoData {
-        token_mint: ctx.accounts.mint.key(),
-        testudo_pubkey: ctx.accounts.testudo.key(),
-    };
 
-    // Check if the user has reached the max number of testudos
-    require_gt!(
+    // Register the testudo if the user has not reached the max number of testudos
+    centurion_data.register_testudo(
+        ctx.accounts.mint.key(),
+        ctx.accounts.testudo.key(),
         ctx.accounts.legate.max_testudos_per_user,
-        centurion_data.testudos.len() as u16,
-        MaxTestudosReached
-    );
-
-    centurion_data.testudos.push(testudo_data);
+    )?;
 
     let current_time: i64 = Clock::get()?.unix_timestamp;
     centurion_data.last_accessed = current_time as u64;
@@ -1 +1,5 @@
 pub mod update_back_up_account;
+
+pub mod reconcile_centurion;
+
+pub mod set_third_party_deposits;
@@ -0,0 +1,52 @@
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority};
+use crate::events::CenturionReconciled;
+use anchor_lang::prelude::*;
+
+// Recompute `lamport_balance` from the Centurion's real lamports. SOL sent straight to the Centurion PDA is
+// credited to the owner, and a balance tracked above what the account holds is brought back down.
+
+#[derive(Accounts)]
+pub struct ReconcileCenturion<'info> {
+    // SIGNERS
+    pub authority: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+}
+
+pub fn process_reconcile_centurion(ctx: Context<ReconcileCenturion>) -> Result<()> {
+    let centurion_info = ctx.accounts.centurion.to_account_info();
+
+    // Everything above the rent-exempt minimum belongs to the owner
+    let min_rent = Rent::get()?.minimum_balance(centurion_info.data_len());
+    let reconciled_balance = centurion_info.lamports().saturating_sub(min_rent);
+
+    let centurion_data: &mut Account<'_, Centurion> = &mut ctx.accounts.centurion;
+    let previous_balance = centurion_data.lamport_balance;
+    if previous_balance != reconciled_balance {
+        msg!(
+            "Reconciling lamport_balance from {} to {}",
+            previous_balance,
+            reconciled_balance
+        );
+        centurion_data.lamport_balance = reconciled_balance;
+        emit!(CenturionReconciled {
+            centurion: centurion_data.key(),
+            previous_balance,
+            reconciled_balance,
+        });
+    }
+
+    centurion_data.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
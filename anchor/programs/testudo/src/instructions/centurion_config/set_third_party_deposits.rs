@@ -0,0 +1,40 @@
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
+};
+use anchor_lang::prelude::*;
+
+// Allow or stop deposits into this Centurion from signers other than its owner
+
+#[derive(Accounts)]
+pub struct SetThirdPartyDeposits<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion", authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+}
+
+pub fn process_set_third_party_deposits(
+    ctx: Context<SetThirdPartyDeposits>,
+    enabled: bool,
+) -> Result<()> {
+    let centurion = &mut ctx.accounts.centurion;
+    centurion.accepts_third_party_deposits = enabled;
+    centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+    Ok(())
+}
@@ -1,6 +1,6 @@
 use crate::custom_accounts::centurion::Centurion;
 use crate::errors::ErrorCode::{
-    CenturionNotInitialized, InvalidAuthority, InvalidPasswordSignature,
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
 };
 use anchor_lang::prelude::*;
 
@@ -23,6 +23,7 @@ pub struct UpdateBackUpAccount<'info> {
         seeds = [b"centurion", authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
@@ -0,0 +1,54 @@
+use crate::custom_accounts::{centurion::Centurion, conditional_order::ConditionalOrder};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
+};
+use anchor_lang::prelude::*;
+
+// Cancel a conditional order. The order's rent and keeper tip are returned to the authority.
+
+#[derive(Accounts)]
+pub struct CancelConditionalOrder<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // DCA ORDER
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"conditional_order".as_ref(), centurion.key().as_ref(), conditional_order.order_id.to_le_bytes().as_ref()],
+        bump = conditional_order.bump,
+        has_one = authority @InvalidAuthority,
+        has_one = centurion @InvalidAuthority,
+    )]
+    pub conditional_order: Account<'info, ConditionalOrder>,
+}
+
+pub fn process_cancel_conditional_order(ctx: Context<CancelConditionalOrder>) -> Result<()> {
+    require_eq!(
+        ctx.accounts.centurion.pubkey_to_password,
+        ctx.accounts.valid_signer_of_password.key(),
+        InvalidPasswordSignature
+    );
+
+    msg!(
+        "Cancelled conditional order {}",
+        ctx.accounts.conditional_order.order_id
+    );
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use crate::constants::{FEE_PERCENT_DENOMINATOR, WSOL_MINT};
+use crate::custom_accounts::{
+    centurion::Centurion,
+    conditional_order::{ConditionalOrder, OrderTrigger},
+    legate::Legate,
+};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidConditionalOrder,
+    InvalidPasswordSignature, LegateNotInitialized, LegateNotMigrated, MissingPriceFeed,
+    OrderTestudoNotRegistered, UnsupportedTokenMint,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::Mint;
+
+// Create a stop-loss or take-profit order for a Centurion. The password approves the trade up front,
+// and the keeper tip is prefunded into the order account by the authority. The output must cover both the
+// order's minimum and the oracle value of what it sells, less the order's slippage allowance.
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateConditionalOrder<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // CONDITIONAL ORDER
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + ConditionalOrder::INIT_SPACE,
+        seeds = [b"conditional_order".as_ref(), centurion.key().as_ref(), order_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub conditional_order: Account<'info, ConditionalOrder>,
+
+    // MINTS
+    #[account(
+        constraint = legate.testudo_token_whitelist.iter().any(|t| t.token_mint == source_mint.key()) @UnsupportedTokenMint
+    )]
+    pub source_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        constraint = legate.testudo_token_whitelist.iter().any(|t| t.token_mint == destination_mint.key()) @UnsupportedTokenMint
+    )]
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn process_create_conditional_order(
+    ctx: Context<CreateConditionalOrder>,
+    order_id: u64,
+    amount_in: u64,
+    min_amount_out: u64,
+    trigger: OrderTrigger,
+    trigger_price: u64, // In micro-USD
+    keeper_tip_lamports: u64,
+    max_slippage_bps: u16,
+) -> Result<()> {
+    require_eq!(
+        ctx.accounts.centurion.pubkey_to_password,
+        ctx.accounts.valid_signer_of_password.key(),
+        InvalidPasswordSignature
+    );
+
+    let source_mint = ctx.accounts.source_mint.key();
+    let destination_mint = ctx.accounts.destination_mint.key();
+    require_neq!(source_mint, destination_mint, InvalidConditionalOrder);
+    require_gt!(amount_in, 0, InvalidConditionalOrder);
+    require_gt!(trigger_price, 0, InvalidConditionalOrder);
+    // Keepers pick the route, so an order without a floor could be filled at any price
+    require_gt!(min_amount_out, 0, InvalidConditionalOrder);
+    require_gte!(
+        FEE_PERCENT_DENOMINATOR,
+        max_slippage_bps,
+        InvalidConditionalOrder
+    );
+
+    // The order watches the source mint's price feed, and both feeds bound its output
+    let legate = &ctx.accounts.legate;
+    for mint in [source_mint, destination_mint] {
+        require_eq!(
+            legate.price_feed_for_mint(&mint).is_some(),
+            true,
+            MissingPriceFeed
+        );
+    }
+
+    // Keepers never pay for testudos. SOL sides use the wSOL testudo, or the tracked balance without one
+    let centurion = &ctx.accounts.centurion;
+    for mint in [source_mint, destination_mint] {
+        require_eq!(
+            mint == WSOL_MINT || centurion.testudos.iter().any(|t| t.token_mint == mint),
+            true,
+            OrderTestudoNotRegistered
+        );
+    }
+
+    // Prefund the keeper tip
+    if keeper_tip_lamports > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.conditional_order.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+            keeper_tip_lamports,
+        )?;
+    }
+
+    let conditional_order = &mut ctx.accounts.conditional_order;
+    conditional_order.authority = ctx.accounts.authority.key();
+    conditional_order.centurion = ctx.accounts.centurion.key();
+    conditional_order.order_id = order_id;
+    conditional_order.bump = ctx.bumps.conditional_order;
+    conditional_order.source_mint = source_mint;
+    conditional_order.destination_mint = destination_mint;
+    conditional_order.amount_in = amount_in;
+    conditional_order.min_amount_out = min_amount_out;
+    conditional_order.trigger = trigger;
+    conditional_order.trigger_price = trigger_price;
+    conditional_order.keeper_tip_lamports = keeper_tip_lamports;
+    conditional_order.created_at = Clock::get()?.unix_timestamp as u64;
+    conditional_order.max_slippage_bps = max_slippage_bps;
+
+    Ok(())
+}
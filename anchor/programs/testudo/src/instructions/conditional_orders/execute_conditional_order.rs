@@ -0,0 +1,248 @@
+use crate::constants::WSOL_MINT;
+use crate::custom_accounts::{
+    centurion::Centurion, conditional_order::ConditionalOrder, legate::Legate,
+};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidTokenMint,
+    InvalidTreasuryAccount, LegateNotInitialized, LegateNotMigrated, OrderAmountMismatch,
+    OrderTestudoNotRegistered, TriggerNotReached, UnapprovedSwapRouter,
+};
+use crate::events::ConditionalOrderExecuted;
+use crate::instructions::swaps::execute::{execute_swap, SwapExecution};
+use crate::instructions::swaps::native_sol::native_sol_seed;
+use crate::instructions::swaps::router::RouterInstructionWithIdxs;
+use crate::oracle::{mint_price_usd, oracle_min_amount_out};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+// Execute a conditional order once its trigger is reached. Permissionless: any keeper may call it and is
+// paid the order's tip. The price comes from the source mint's registered feed and must be fresh and confident.
+// The destination mint's feed sets the least the swap may return, whatever route the keeper picks.
+
+#[derive(Accounts)]
+pub struct ExecuteConditionalOrder<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    // ORDER OWNER. Receives the order account once it has run
+    #[account(mut, address = conditional_order.authority @InvalidAuthority)]
+    /// CHECK: Checked against the order's authority
+    pub authority: UncheckedAccount<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), conditional_order.authority.as_ref()],
+        bump = centurion.bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // CONDITIONAL ORDER
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"conditional_order".as_ref(), centurion.key().as_ref(), conditional_order.order_id.to_le_bytes().as_ref()],
+        bump = conditional_order.bump,
+        has_one = centurion @InvalidAuthority,
+    )]
+    pub conditional_order: Account<'info, ConditionalOrder>,
+
+    // PRICE FEED OF THE SOURCE MINT
+    /// CHECK: Must be the price feed registered on the Legate. Checked and parsed by the oracle module
+    pub price_account: UncheckedAccount<'info>,
+
+    // PRICE FEED OF THE DESTINATION MINT
+    /// CHECK: Must be the price feed registered on the Legate. Checked and parsed by the oracle module
+    pub destination_price_account: UncheckedAccount<'info>,
+
+    // SOURCE TOKEN ACCOUNT. For SOL without a wSOL testudo, a temporary wSOL account whose rent the keeper gets back
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        token::mint = source_mint,
+        token::authority = centurion,
+        token::token_program = source_token_program,
+        seeds = [centurion.key().as_ref(), source_mint.key().as_ref(), native_sol_seed(&centurion, &source_mint.key())],
+        bump
+    )]
+    pub source_testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // DESTINATION TOKEN ACCOUNT. For SOL without a wSOL testudo, a temporary wSOL account whose rent the keeper gets back
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        token::mint = destination_mint,
+        token::authority = centurion,
+        token::token_program = destination_token_program,
+        seeds = [centurion.key().as_ref(), destination_mint.key().as_ref(), native_sol_seed(&centurion, &destination_mint.key())],
+        bump
+    )]
+    pub destination_testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // MINTS
+    #[account(
+        address = conditional_order.source_mint @InvalidTokenMint,
+        mint::token_program = source_token_program,
+    )]
+    pub source_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        address = conditional_order.destination_mint @InvalidTokenMint,
+        mint::token_program = destination_token_program,
+    )]
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+
+    // TREASURY
+    #[account(
+        constraint = legate.treasury_acc == treasury.key() @InvalidTreasuryAccount
+    )]
+    /// CHECK: Explicit wrapper for AccountInfo type to emphasize that no checks are performed
+    pub treasury: UncheckedAccount<'info>,
+
+    // TREASURY ATA (DESTINATION MINT). Receives the protocol swap fee
+    #[account(
+        mut,
+        associated_token::mint = destination_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = destination_token_program,
+    )]
+    pub treasury_destination_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        constraint = source_token_program.key() == anchor_spl::token::ID || source_token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub source_token_program: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = destination_token_program.key() == anchor_spl::token::ID || destination_token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub destination_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Any router program approved by the Legate (Jupiter, an AMM, ...)
+    #[account(
+        executable,
+        constraint = legate.approved_swap_routers.contains(&swap_router.key()) @UnapprovedSwapRouter
+    )]
+    pub swap_router: UncheckedAccount<'info>,
+}
+
+pub fn process_execute_conditional_order<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteConditionalOrder<'info>>,
+    router_swap: RouterInstructionWithIdxs,
+    router_setup: Vec<RouterInstructionWithIdxs>,
+    router_cleanup: Option<RouterInstructionWithIdxs>,
+) -> Result<()> {
+    let order = &ctx.accounts.conditional_order;
+
+    // Check the trigger against a fresh, confident price
+    let oracle_price = mint_price_usd(
+        &ctx.accounts.legate,
+        &order.source_mint,
+        &ctx.accounts.price_account.to_account_info(),
+    )?;
+    msg!(
+        "Oracle price: {} micro-USD, trigger: {:?} {}",
+        oracle_price,
+        order.trigger,
+        order.trigger_price
+    );
+    require_eq!(
+        order.trigger.is_reached(oracle_price, order.trigger_price),
+        true,
+        TriggerNotReached
+    );
+
+    // Testudos may have been deleted since the order was placed
+    let centurion = &ctx.accounts.centurion;
+    for mint in [order.source_mint, order.destination_mint] {
+        require_eq!(
+            mint == WSOL_MINT || centurion.testudos.iter().any(|t| t.token_mint == mint),
+            true,
+            OrderTestudoNotRegistered
+        );
+    }
+
+    // Never accept less than the oracle value of the input, less the order's slippage allowance
+    let amount_in = order.amount_in;
+    let destination_price = mint_price_usd(
+        &ctx.accounts.legate,
+        &order.destination_mint,
+        &ctx.accounts.destination_price_account.to_account_info(),
+    )?;
+    let oracle_min = oracle_min_amount_out(
+        amount_in,
+        ctx.accounts.source_mint.decimals,
+        oracle_price,
+        ctx.accounts.destination_mint.decimals,
+        destination_price,
+        order.max_slippage_bps,
+    )?;
+    let min_amount_out = order.min_amount_out.max(oracle_min);
+    let authority_key = order.authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        authority_key.as_ref(),
+        &[ctx.accounts.centurion.bump],
+    ]];
+
+    let outcome = execute_swap(
+        SwapExecution {
+            centurion: &mut ctx.accounts.centurion,
+            legate: &ctx.accounts.legate,
+            source_testudo: &mut ctx.accounts.source_testudo,
+            destination_testudo: &mut ctx.accounts.destination_testudo,
+            source_mint: &ctx.accounts.source_mint,
+            destination_mint: &ctx.accounts.destination_mint,
+            treasury_destination_ata: &ctx.accounts.treasury_destination_ata,
+            source_token_program: &ctx.accounts.source_token_program,
+            destination_token_program: &ctx.accounts.destination_token_program,
+            swap_router: ctx.accounts.swap_router.key(),
+            rent_recipient: ctx.accounts.keeper.to_account_info(),
+            min_amount_out,
+            max_amount_in: amount_in,
+        },
+        ctx.remaining_accounts,
+        router_swap,
+        router_setup,
+        router_cleanup,
+        signer_seeds,
+    )?;
+
+    // The order sells exactly the agreed amount
+    require_eq!(outcome.amount_in, amount_in, OrderAmountMismatch);
+
+    // Pay the keeper from the prefunded tip. The rest of the order account goes back to the authority
+    let keeper_tip = ctx.accounts.conditional_order.keeper_tip_lamports;
+    if keeper_tip > 0 {
+        ctx.accounts.conditional_order.sub_lamports(keeper_tip)?;
+        ctx.accounts.keeper.add_lamports(keeper_tip)?;
+    }
+
+    emit!(ConditionalOrderExecuted {
+        conditional_order: ctx.accounts.conditional_order.key(),
+        centurion: ctx.accounts.centurion.key(),
+        keeper: ctx.accounts.keeper.key(),
+        oracle_price,
+        amount_in: outcome.amount_in,
+        amount_out: outcome.amount_out,
+        fee: outcome.fee,
+        keeper_tip,
+    });
+
+    Ok(())
+}
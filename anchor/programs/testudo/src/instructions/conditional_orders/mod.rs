@@ -0,0 +1,5 @@
+pub mod create_conditional_order;
+
+pub mod cancel_conditional_order;
+
+pub mod execute_conditional_order;
@@ -0,0 +1,56 @@
+use crate::custom_accounts::{centurion::Centurion, dca_order::DcaOrder};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
+};
+use anchor_lang::prelude::*;
+
+// Cancel a DCA order. The order's rent and any unspent keeper tips are returned to the authority.
+
+#[derive(Accounts)]
+pub struct CancelDcaOrder<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // DCA ORDER
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"dca".as_ref(), centurion.key().as_ref(), dca_order.order_id.to_le_bytes().as_ref()],
+        bump = dca_order.bump,
+        has_one = authority @InvalidAuthority,
+        has_one = centurion @InvalidAuthority,
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+}
+
+pub fn process_cancel_dca_order(ctx: Context<CancelDcaOrder>) -> Result<()> {
+    require_eq!(
+        ctx.accounts.centurion.pubkey_to_password,
+        ctx.accounts.valid_signer_of_password.key(),
+        InvalidPasswordSignature
+    );
+
+    msg!(
+        "Cancelled DCA order {} after {} of {} cycles",
+        ctx.accounts.dca_order.order_id,
+        ctx.accounts.dca_order.cycles_executed,
+        ctx.accounts.dca_order.total_cycles
+    );
+    Ok(())
+}
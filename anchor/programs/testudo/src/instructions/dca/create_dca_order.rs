@@ -0,0 +1,155 @@
+use crate::constants::{FEE_PERCENT_DENOMINATOR, WSOL_MINT};
+use crate::custom_accounts::{centurion::Centurion, dca_order::DcaOrder, legate::Legate};
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority,
+    InvalidDcaOrder, InvalidPasswordSignature, LegateNotInitialized, LegateNotMigrated,
+    MissingPriceFeed, OrderTestudoNotRegistered, UnsupportedTokenMint,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::Mint;
+
+// Create a DCA order for a Centurion. The password approves every cycle up front, and the keeper tips
+// for all cycles are prefunded into the order account by the authority. Every cycle must return at least
+// the order's minimum, and optionally the oracle value of what it sells less a slippage allowance.
+
+#[derive(Accounts)]
+#[instruction(order_id: u64)]
+pub struct CreateDcaOrder<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // DCA ORDER
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DcaOrder::INIT_SPACE,
+        seeds = [b"dca".as_ref(), centurion.key().as_ref(), order_id.to_le_bytes().as_ref()],
+        bump,
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    // MINTS
+    #[account(
+        constraint = legate.testudo_token_whitelist.iter().any(|t| t.token_mint == source_mint.key()) @UnsupportedTokenMint
+    )]
+    pub source_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        constraint = legate.testudo_token_whitelist.iter().any(|t| t.token_mint == destination_mint.key()) @UnsupportedTokenMint
+    )]
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+#[allow(clippy::too_many_arguments)]
+pub fn process_create_dca_order(
+    ctx: Context<CreateDcaOrder>,
+    order_id: u64,
+    amount_per_cycle: u64,
+    min_amount_out_per_cycle: u64,
+    interval_seconds: u64,
+    total_cycles: u16,
+    keeper_tip_lamports: u64,
+    max_slippage_bps: Option<u16>,
+) -> Result<()> {
+    require_eq!(
+        ctx.accounts.centurion.pubkey_to_password,
+        ctx.accounts.valid_signer_of_password.key(),
+        InvalidPasswordSignature
+    );
+
+    let source_mint = ctx.accounts.source_mint.key();
+    let destination_mint = ctx.accounts.destination_mint.key();
+    require_neq!(source_mint, destination_mint, InvalidDcaOrder);
+    require_gt!(amount_per_cycle, 0, InvalidDcaOrder);
+    // Keepers pick the route, so an order without a floor could be filled at any price
+    require_gt!(min_amount_out_per_cycle, 0, InvalidDcaOrder);
+    require_gt!(interval_seconds, 0, InvalidDcaOrder);
+    require_gt!(total_cycles, 0, InvalidDcaOrder);
+
+    // Keepers never pay for testudos. SOL sides use the wSOL testudo, or the tracked balance without one
+    let centurion = &ctx.accounts.centurion;
+    for mint in [source_mint, destination_mint] {
+        require_eq!(
+            mint == WSOL_MINT || centurion.testudos.iter().any(|t| t.token_mint == mint),
+            true,
+            OrderTestudoNotRegistered
+        );
+    }
+
+    // An oracle-bounded order needs both mints priced by the Legate
+    if let Some(max_slippage_bps) = max_slippage_bps {
+        require_gte!(FEE_PERCENT_DENOMINATOR, max_slippage_bps, InvalidDcaOrder);
+        let legate = &ctx.accounts.legate;
+        for mint in [source_mint, destination_mint] {
+            require_eq!(
+                legate.price_feed_for_mint(&mint).is_some(),
+                true,
+                MissingPriceFeed
+            );
+        }
+    }
+
+    // Prefund the keeper tips for every cycle
+    let total_tips = keeper_tip_lamports
+        .checked_mul(total_cycles as u64)
+        .ok_or(ArithmeticOverflow)?;
+    if total_tips > 0 {
+        let cpi_accounts = system_program::Transfer {
+            from: ctx.accounts.authority.to_account_info(),
+            to: ctx.accounts.dca_order.to_account_info(),
+        };
+        system_program::transfer(
+            CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+            total_tips,
+        )?;
+    }
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let dca_order = &mut ctx.accounts.dca_order;
+    dca_order.authority = ctx.accounts.authority.key();
+    dca_order.centurion = ctx.accounts.centurion.key();
+    dca_order.order_id = order_id;
+    dca_order.bump = ctx.bumps.dca_order;
+    dca_order.source_mint = source_mint;
+    dca_order.destination_mint = destination_mint;
+    dca_order.amount_per_cycle = amount_per_cycle;
+    dca_order.min_amount_out_per_cycle = min_amount_out_per_cycle;
+    dca_order.interval_seconds = interval_seconds;
+    dca_order.total_cycles = total_cycles;
+    dca_order.cycles_executed = 0;
+    // The first cycle is due immediately
+    dca_order.next_execution_at = current_time;
+    dca_order.keeper_tip_lamports = keeper_tip_lamports;
+    dca_order.created_at = current_time;
+    dca_order.max_slippage_bps = max_slippage_bps;
+
+    Ok(())
+}
@@ -0,0 +1,253 @@
+use crate::constants::WSOL_MINT;
+use crate::custom_accounts::{centurion::Centurion, dca_order::DcaOrder, legate::Legate};
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, DcaCycleAmountMismatch,
+    DcaCycleNotDue, DcaOrderCompleted, InvalidAuthority, InvalidTokenMint, InvalidTreasuryAccount,
+    LegateNotInitialized, LegateNotMigrated, MissingPriceFeed, OrderTestudoNotRegistered,
+    UnapprovedSwapRouter,
+};
+use crate::events::DcaCycleExecuted;
+use crate::instructions::swaps::execute::{execute_swap, SwapExecution};
+use crate::instructions::swaps::native_sol::native_sol_seed;
+use crate::instructions::swaps::router::RouterInstructionWithIdxs;
+use crate::oracle::{mint_price_usd, oracle_min_amount_out};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::AssociatedToken,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+// Execute one due cycle of a DCA order. Permissionless: any keeper may call it and is paid the order's tip.
+// The keeper chooses the route, but the order fixes what is sold and the minimum that must come back.
+
+#[derive(Accounts)]
+pub struct ExecuteDcaCycle<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub keeper: Signer<'info>,
+
+    // ORDER OWNER. Receives the order account once its last cycle has run
+    #[account(mut, address = dca_order.authority @InvalidAuthority)]
+    /// CHECK: Checked against the order's authority
+    pub authority: UncheckedAccount<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), dca_order.authority.as_ref()],
+        bump = centurion.bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // DCA ORDER
+    #[account(
+        mut,
+        seeds = [b"dca".as_ref(), centurion.key().as_ref(), dca_order.order_id.to_le_bytes().as_ref()],
+        bump = dca_order.bump,
+        has_one = centurion @InvalidAuthority,
+    )]
+    pub dca_order: Account<'info, DcaOrder>,
+
+    // SOURCE TOKEN ACCOUNT. For SOL without a wSOL testudo, a temporary wSOL account whose rent the keeper gets back
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        token::mint = source_mint,
+        token::authority = centurion,
+        token::token_program = source_token_program,
+        seeds = [centurion.key().as_ref(), source_mint.key().as_ref(), native_sol_seed(&centurion, &source_mint.key())],
+        bump
+    )]
+    pub source_testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // DESTINATION TOKEN ACCOUNT. For SOL without a wSOL testudo, a temporary wSOL account whose rent the keeper gets back
+    #[account(
+        init_if_needed,
+        payer = keeper,
+        token::mint = destination_mint,
+        token::authority = centurion,
+        token::token_program = destination_token_program,
+        seeds = [centurion.key().as_ref(), destination_mint.key().as_ref(), native_sol_seed(&centurion, &destination_mint.key())],
+        bump
+    )]
+    pub destination_testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // MINTS
+    #[account(
+        address = dca_order.source_mint @InvalidTokenMint,
+        mint::token_program = source_token_program,
+    )]
+    pub source_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        address = dca_order.destination_mint @InvalidTokenMint,
+        mint::token_program = destination_token_program,
+    )]
+    pub destination_mint: InterfaceAccount<'info, Mint>,
+
+    // PRICE ACCOUNTS. Required when the order is oracle-bounded; checked against the Legate's feeds
+    /// CHECK: Parsed by the oracle module after matching the source mint's registered feed
+    pub source_price_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Parsed by the oracle module after matching the destination mint's registered feed
+    pub destination_price_account: Option<UncheckedAccount<'info>>,
+
+    // TREASURY
+    #[account(
+        constraint = legate.treasury_acc == treasury.key() @InvalidTreasuryAccount
+    )]
+    /// CHECK: Explicit wrapper for AccountInfo type to emphasize that no checks are performed
+    pub treasury: UncheckedAccount<'info>,
+
+    // TREASURY ATA (DESTINATION MINT). Receives the protocol swap fee
+    #[account(
+        mut,
+        associated_token::mint = destination_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = destination_token_program,
+    )]
+    pub treasury_destination_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        constraint = source_token_program.key() == anchor_spl::token::ID || source_token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub source_token_program: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = destination_token_program.key() == anchor_spl::token::ID || destination_token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub destination_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Any router program approved by the Legate (Jupiter, an AMM, ...)
+    #[account(
+        executable,
+        constraint = legate.approved_swap_routers.contains(&swap_router.key()) @UnapprovedSwapRouter
+    )]
+    pub swap_router: UncheckedAccount<'info>,
+}
+
+pub fn process_execute_dca_cycle<'info>(
+    ctx: Context<'_, '_, '_, 'info, ExecuteDcaCycle<'info>>,
+    router_swap: RouterInstructionWithIdxs,
+    router_setup: Vec<RouterInstructionWithIdxs>,
+    router_cleanup: Option<RouterInstructionWithIdxs>,
+    min_amount_out: u64, // Keeper's own bound. The order's minimum always applies as well
+) -> Result<()> {
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let order = &ctx.accounts.dca_order;
+
+    require_gt!(order.total_cycles, order.cycles_executed, DcaOrderCompleted);
+    require_gte!(current_time, order.next_execution_at, DcaCycleNotDue);
+
+    // Testudos may have been deleted since the order was placed
+    let centurion = &ctx.accounts.centurion;
+    for mint in [order.source_mint, order.destination_mint] {
+        require_eq!(
+            mint == WSOL_MINT || centurion.testudos.iter().any(|t| t.token_mint == mint),
+            true,
+            OrderTestudoNotRegistered
+        );
+    }
+
+    let amount_per_cycle = order.amount_per_cycle;
+    let mut min_amount_out = min_amount_out.max(order.min_amount_out_per_cycle);
+    if let Some(max_slippage_bps) = order.max_slippage_bps {
+        let legate = &ctx.accounts.legate;
+        let source_price_account = ctx
+            .accounts
+            .source_price_account
+            .as_ref()
+            .ok_or(MissingPriceFeed)?;
+        let destination_price_account = ctx
+            .accounts
+            .destination_price_account
+            .as_ref()
+            .ok_or(MissingPriceFeed)?;
+        let oracle_min = oracle_min_amount_out(
+            amount_per_cycle,
+            ctx.accounts.source_mint.decimals,
+            mint_price_usd(legate, &order.source_mint, source_price_account)?,
+            ctx.accounts.destination_mint.decimals,
+            mint_price_usd(legate, &order.destination_mint, destination_price_account)?,
+            max_slippage_bps,
+        )?;
+        min_amount_out = min_amount_out.max(oracle_min);
+    }
+    let authority_key = order.authority;
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        authority_key.as_ref(),
+        &[ctx.accounts.centurion.bump],
+    ]];
+
+    let outcome = execute_swap(
+        SwapExecution {
+            centurion: &mut ctx.accounts.centurion,
+            legate: &ctx.accounts.legate,
+            source_testudo: &mut ctx.accounts.source_testudo,
+            destination_testudo: &mut ctx.accounts.destination_testudo,
+            source_mint: &ctx.accounts.source_mint,
+            destination_mint: &ctx.accounts.destination_mint,
+            treasury_destination_ata: &ctx.accounts.treasury_destination_ata,
+            source_token_program: &ctx.accounts.source_token_program,
+            destination_token_program: &ctx.accounts.destination_token_program,
+            swap_router: ctx.accounts.swap_router.key(),
+            rent_recipient: ctx.accounts.keeper.to_account_info(),
+            min_amount_out,
+            max_amount_in: amount_per_cycle,
+        },
+        ctx.remaining_accounts,
+        router_swap,
+        router_setup,
+        router_cleanup,
+        signer_seeds,
+    )?;
+
+    // Each cycle sells exactly the agreed amount, so a keeper cannot collect tips for token trades
+    require_eq!(outcome.amount_in, amount_per_cycle, DcaCycleAmountMismatch);
+
+    let dca_order = &mut ctx.accounts.dca_order;
+    dca_order.cycles_executed = dca_order
+        .cycles_executed
+        .checked_add(1)
+        .ok_or(ArithmeticOverflow)?;
+    dca_order.next_execution_at = dca_order
+        .next_execution_at
+        .checked_add(dca_order.interval_seconds)
+        .ok_or(ArithmeticOverflow)?;
+
+    // Pay the keeper from the prefunded tips
+    let keeper_tip = dca_order.keeper_tip_lamports;
+    if keeper_tip > 0 {
+        dca_order.sub_lamports(keeper_tip)?;
+        ctx.accounts.keeper.add_lamports(keeper_tip)?;
+    }
+
+    emit!(DcaCycleExecuted {
+        dca_order: dca_order.key(),
+        centurion: ctx.accounts.centurion.key(),
+        keeper: ctx.accounts.keeper.key(),
+        cycle: dca_order.cycles_executed,
+        amount_in: outcome.amount_in,
+        amount_out: outcome.amount_out,
+        fee: outcome.fee,
+        keeper_tip,
+    });
+
+    // Close the order once its last cycle has run
+    if dca_order.cycles_executed == dca_order.total_cycles {
+        dca_order.close(ctx.accounts.authority.to_account_info())?;
+    }
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod create_dca_order;
+
+pub mod cancel_dca_order;
+
+pub mod execute_dca_cycle;
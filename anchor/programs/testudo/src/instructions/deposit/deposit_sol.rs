@@ -1,6 +1,7 @@
 use crate::custom_accounts::centurion::Centurion;
 use crate::errors::ErrorCode::{
-    ArithmeticOverflow, CenturionNotInitialized, InsufficientFunds, InvalidAuthority,
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds,
+    InvalidAuthority,
 };
 use anchor_lang::prelude::*;
 use anchor_lang::system_program;
@@ -19,6 +20,7 @@ pub struct DepositSol<'info> {
         seeds = [b"centurion".as_ref(), authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
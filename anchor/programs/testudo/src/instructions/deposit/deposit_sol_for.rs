@@ -0,0 +1,79 @@
+use crate::constants::{MAX_DEPOSIT_MEMO_LEN, WSOL_MINT};
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, DepositMemoTooLong,
+    InsufficientFunds, ThirdPartyDepositsDisabled,
+};
+use crate::events::ThirdPartyDeposit;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+
+// Deposit native SOL into someone else's Centurion. Any signer can pay in once the owner has turned
+// third-party deposits on; the optional memo is emitted for the owner to match the payment.
+
+#[derive(Accounts)]
+pub struct DepositSolFor<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), centurion.authority.as_ref()],
+        bump = centurion.bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        constraint = centurion.accepts_third_party_deposits @ThirdPartyDepositsDisabled,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_deposit_sol_for(
+    ctx: Context<DepositSolFor>,
+    amount_in_lamports: u64,
+    memo: Option<String>,
+) -> Result<()> {
+    if let Some(memo) = &memo {
+        require_gte!(MAX_DEPOSIT_MEMO_LEN, memo.len(), DepositMemoTooLong);
+    }
+
+    // Ensure the depositor has enough funds to deposit
+    require_gte!(
+        ctx.accounts.depositor.get_lamports(),
+        amount_in_lamports,
+        InsufficientFunds
+    );
+
+    system_program::transfer(
+        CpiContext::new(
+            ctx.accounts.system_program.to_account_info(),
+            system_program::Transfer {
+                from: ctx.accounts.depositor.to_account_info(),
+                to: ctx.accounts.centurion.to_account_info(),
+            },
+        ),
+        amount_in_lamports,
+    )?;
+
+    // last_accessed is left alone; it tracks the owner's activity
+    let centurion = &mut ctx.accounts.centurion;
+    centurion.lamport_balance = centurion
+        .lamport_balance
+        .checked_add(amount_in_lamports)
+        .ok_or(ArithmeticOverflow)?;
+
+    emit!(ThirdPartyDeposit {
+        centurion: centurion.key(),
+        depositor: ctx.accounts.depositor.key(),
+        mint: WSOL_MINT,
+        amount: amount_in_lamports,
+        amount_received: amount_in_lamports,
+        memo,
+    });
+
+    Ok(())
+}
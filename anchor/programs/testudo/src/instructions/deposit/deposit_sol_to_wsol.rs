@@ -0,0 +1,103 @@
+use crate::constants::WSOL_MINT;
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds, InvalidAuthority,
+    InvalidTokenMint,
+};
+use crate::events::SplDeposited;
+use anchor_lang::prelude::*;
+use anchor_lang::system_program;
+use anchor_spl::token_interface::{sync_native, Mint, SyncNative, TokenAccount, TokenInterface};
+
+// Deposit native SOL straight into the Centurion's wSOL testudo, so it is held like any other mint.
+
+#[derive(Accounts)]
+pub struct DepositSolToWsol<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // WSOL TESTUDO
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+        seeds = [centurion.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // MINT
+    #[account(
+        address = WSOL_MINT @InvalidTokenMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // PROGRAMS
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_deposit_sol_to_wsol(
+    ctx: Context<DepositSolToWsol>,
+    amount_in_lamports: u64,
+) -> Result<()> {
+    // Ensure the wSOL testudo is registered with the Centurion
+    require_eq!(
+        ctx.accounts
+            .centurion
+            .testudos
+            .iter()
+            .any(|testudo| testudo.token_mint == WSOL_MINT),
+        true,
+        InvalidTokenMint
+    );
+    // Ensure the authority has enough funds to deposit
+    require_gte!(
+        ctx.accounts.authority.get_lamports(),
+        amount_in_lamports,
+        InsufficientFunds
+    );
+
+    // Send the SOL to the wSOL testudo, then sync its token balance with its lamports
+    let cpi_accounts = system_program::Transfer {
+        from: ctx.accounts.authority.to_account_info(),
+        to: ctx.accounts.testudo.to_account_info(),
+    };
+    system_program::transfer(
+        CpiContext::new(ctx.accounts.system_program.to_account_info(), cpi_accounts),
+        amount_in_lamports,
+    )?;
+    sync_native(CpiContext::new(
+        ctx.accounts.token_program.to_account_info(),
+        SyncNative {
+            account: ctx.accounts.testudo.to_account_info(),
+        },
+    ))?;
+
+    emit!(SplDeposited {
+        centurion: ctx.accounts.centurion.key(),
+        mint: WSOL_MINT,
+        amount: amount_in_lamports,
+        amount_received: amount_in_lamports,
+    });
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -1,10 +1,12 @@
-use crate::custom_accounts::centurion::Centurion;
+use crate::custom_accounts::{centurion::Centurion, legate::Legate};
 use crate::errors::ErrorCode::{
-    CenturionNotInitialized, InsufficientFunds, InvalidATA, InvalidAuthority, InvalidTokenMint,
+    CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds, InvalidATA, InvalidAuthority,
+    InvalidTokenMint, LegateNotInitialized, LegateNotMigrated,
 };
+use crate::events::SplDeposited;
+use crate::transfers::{require_approved_transfer_hook, transfer_tokens};
 use anchor_lang::prelude::*;
-use anchor_spl::token_interface;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 // Deposit SPL tokens into a testudo account. authority is the sender and Centurion is the receiver.
 
@@ -29,10 +31,20 @@ pub struct DepositSplToken<'info> {
         seeds = [b"centurion".as_ref(), authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
 
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
     // TESTUDO TOKEN ACCOUNT
     #[account(
         mut,
@@ -65,8 +77,8 @@ pub struct DepositSplToken<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn process_deposit_spl_token(
-    ctx: Context<DepositSplToken>,
+pub fn process_deposit_spl_token<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositSplToken<'info>>,
     amount_with_decimals: u64,
 ) -> Result<()> {
     let centurion_data: &mut Account<'_, Centurion> = &mut ctx.accounts.centurion;
@@ -80,6 +92,9 @@ pub fn process_deposit_spl_token(
         InvalidTokenMint
     );
 
+    // Hooked mints must still point at the hook program approved on their whitelist entry
+    require_approved_transfer_hook(&ctx.accounts.legate, &ctx.accounts.mint)?;
+
     // Get the amount of tokens the depositor has in their ATA
     let depositer_token_holdings: u64 = ctx.accounts.authority_ata.amount;
 
@@ -90,26 +105,24 @@ pub fn process_deposit_spl_token(
         InsufficientFunds
     );
 
-    // Get the testudo account for the token
-    let testudo_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.testudo;
-    // Get the depositor's ATA for the token
-    let authority_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.authority_ata;
-
-    // Set up the CPI accounts for the transfer
-    let cpi_accounts = TransferChecked {
-        from: authority_ata.to_account_info(),
-        to: testudo_ata.to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        authority: ctx.accounts.authority.to_account_info(),
-    };
-
-    let decimals: u8 = ctx.accounts.mint.decimals;
-
-    // Set up the CPI context for the transfer
-    let cpi_context: CpiContext<'_, '_, '_, '_, TransferChecked<'_>> =
-        CpiContext::new(ctx.accounts.token_program.to_account_info(), cpi_accounts);
-    // Perform the transfer
-    token_interface::transfer_checked(cpi_context, amount_with_decimals, decimals)?;
+    // Perform the transfer. Mints with a transfer fee deliver less than was sent
+    let amount_received = transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.authority_ata.to_account_info(),
+        ctx.accounts.testudo.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.mint,
+        amount_with_decimals,
+        &[],
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(SplDeposited {
+        centurion: centurion_data.key(),
+        mint: ctx.accounts.mint.key(),
+        amount: amount_with_decimals,
+        amount_received,
+    });
 
     // Update the last accessed timestamp
     let current_datetime: i64 = Clock::get()?.unix_timestamp;
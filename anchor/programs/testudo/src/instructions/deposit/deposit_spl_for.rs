@@ -0,0 +1,121 @@
+use crate::constants::MAX_DEPOSIT_MEMO_LEN;
+use crate::custom_accounts::{centurion::Centurion, legate::Legate};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, DepositMemoTooLong, InsufficientFunds,
+    InvalidATA, InvalidTokenMint, LegateNotInitialized, LegateNotMigrated,
+    ThirdPartyDepositsDisabled,
+};
+use crate::events::ThirdPartyDeposit;
+use crate::transfers::{require_approved_transfer_hook, transfer_tokens};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Deposit SPL tokens into a testudo of someone else's Centurion. Any signer can pay in once the owner has
+// turned third-party deposits on; the optional memo is emitted for the owner to match the payment.
+
+#[derive(Accounts)]
+pub struct DepositSplTokenFor<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub depositor: Signer<'info>,
+
+    // DEPOSITOR TOKEN ACCOUNT. Any account the depositor owns, not only their ATA
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = depositor,
+        token::token_program = token_program,
+    )]
+    pub depositor_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), centurion.authority.as_ref()],
+        bump = centurion.bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        constraint = centurion.accepts_third_party_deposits @ThirdPartyDepositsDisabled,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // TESTUDO TOKEN ACCOUNT
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+        seeds = [centurion.key().as_ref(), mint.key().as_ref()],
+        bump,
+        // Ensure the ATA is for the correct Centurion (User)
+        constraint = testudo.owner == centurion.key() @InvalidATA,
+    )]
+    pub testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // MINT
+    #[account(
+        mut,
+        // Ensure the Centurion has a testudo for this mint
+        constraint = centurion.testudos.iter().any(|t| t.token_mint == mint.key()) @InvalidTokenMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // PROGRAMS
+    // Ensure valid token program is passed
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID || token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn process_deposit_spl_token_for<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositSplTokenFor<'info>>,
+    amount_with_decimals: u64,
+    memo: Option<String>,
+) -> Result<()> {
+    if let Some(memo) = &memo {
+        require_gte!(MAX_DEPOSIT_MEMO_LEN, memo.len(), DepositMemoTooLong);
+    }
+
+    // Hooked mints must still point at the hook program approved on their whitelist entry
+    require_approved_transfer_hook(&ctx.accounts.legate, &ctx.accounts.mint)?;
+
+    // Ensure the depositor has enough tokens to cover the deposit
+    require_gte!(
+        ctx.accounts.depositor_token_account.amount,
+        amount_with_decimals,
+        InsufficientFunds
+    );
+
+    // Mints with a transfer fee deliver less than was sent
+    let amount_received = transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.depositor_token_account.to_account_info(),
+        ctx.accounts.testudo.to_account_info(),
+        ctx.accounts.depositor.to_account_info(),
+        &ctx.accounts.mint,
+        amount_with_decimals,
+        &[],
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(ThirdPartyDeposit {
+        centurion: ctx.accounts.centurion.key(),
+        depositor: ctx.accounts.depositor.key(),
+        mint: ctx.accounts.mint.key(),
+        amount: amount_with_decimals,
+        amount_received,
+        memo,
+    });
+
+    Ok(())
+}
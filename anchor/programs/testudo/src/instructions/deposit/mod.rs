@@ -1,3 +1,9 @@
 pub mod deposit_sol;
 
 pub mod deposit_spl;
+
+pub mod deposit_sol_to_wsol;
+
+pub mod deposit_sol_for;
+
+pub mod deposit_spl_for;
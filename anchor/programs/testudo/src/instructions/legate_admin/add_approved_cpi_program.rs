@@ -0,0 +1,61 @@
+use crate::constants::MAX_APPROVED_CPI_PROGRAMS;
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    CpiProgramAlreadyApproved, InvalidAuthority, LegateNotInitialized, LegateNotMigrated,
+    MaxApprovedCpiProgramsReached,
+};
+use anchor_lang::prelude::*;
+
+// Approve a program that may be invoked with the Centurion signer during a swap
+
+#[derive(Accounts)]
+pub struct AddApprovedCpiProgram<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_add_approved_cpi_program(
+    ctx: Context<AddApprovedCpiProgram>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // check if program is already in the list
+    if legate.approved_cpi_programs.contains(&program_id) {
+        return Err(CpiProgramAlreadyApproved.into());
+    }
+    // Ensure max approved programs is not reached
+    require_gt!(
+        MAX_APPROVED_CPI_PROGRAMS,
+        legate.approved_cpi_programs.len(),
+        MaxApprovedCpiProgramsReached
+    );
+
+    legate.approved_cpi_programs.push(program_id);
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -1,8 +1,10 @@
+use crate::constants::FEE_PERCENT_DENOMINATOR;
 use crate::custom_accounts::legate::{Legate, TestudoTokenWhitelist};
 use crate::errors::ErrorCode::{
-    InvalidAuthority, InvalidTreasuryAccount, LegateNotInitialized, MaxWhitelistedMintsReached,
-    MintAlreadyInList,
+    InvalidAuthority, InvalidFeePercent, InvalidTreasuryAccount, LegateNotInitialized,
+    LegateNotMigrated, MaxWhitelistedMintsReached, MintAlreadyInList,
 };
+use crate::mint_risk::inspect_mint_risk;
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -22,6 +24,7 @@ pub struct AddMintToTestudoTokenWhitelist<'info> {
         bump,
         has_one = authority @InvalidAuthority,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 
@@ -65,7 +68,7 @@ pub struct AddMintToTestudoTokenWhitelist<'info> {
 
 pub fn process_add_mint_to_testudo_token_whitelist(
     ctx: Context<AddMintToTestudoTokenWhitelist>,
-    mint_to_add: TestudoTokenWhitelist,
+    mut mint_to_add: TestudoTokenWhitelist,
 ) -> Result<()> {
     let legate = &mut ctx.accounts.legate;
 
@@ -92,6 +95,14 @@ pub fn process_add_mint_to_testudo_token_whitelist(
         legate.testudo_token_whitelist.len() as u16,
         MaxWhitelistedMintsReached
     );
+    // Ensure any per-mint fee override is a valid percentage
+    if let Some(fee_percent) = mint_to_add.percent_for_fees {
+        require_gte!(FEE_PERCENT_DENOMINATOR, fee_percent, InvalidFeePercent);
+    }
+
+    // Reject mints whose issuer can seize or lock funds, and record the remaining risks
+    mint_to_add.risk_flags =
+        inspect_mint_risk(&ctx.accounts.mint, mint_to_add.transfer_hook_program)?;
 
     legate.testudo_token_whitelist.push(mint_to_add);
     Ok(())
@@ -0,0 +1,61 @@
+use crate::constants::MAX_NFT_COLLECTIONS;
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, MaxNftCollectionsReached,
+    NftCollectionAlreadyApproved,
+};
+use anchor_lang::prelude::*;
+
+// Approve a verified collection whose NFTs can be vaulted in a Centurion
+
+#[derive(Accounts)]
+pub struct AddNftCollection<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_add_nft_collection(
+    ctx: Context<AddNftCollection>,
+    collection: Pubkey,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // check if collection is already in the list
+    if legate.nft_collections.contains(&collection) {
+        return Err(NftCollectionAlreadyApproved.into());
+    }
+    // Ensure max NFT collections is not reached
+    require_gt!(
+        MAX_NFT_COLLECTIONS,
+        legate.nft_collections.len(),
+        MaxNftCollectionsReached
+    );
+
+    legate.nft_collections.push(collection);
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
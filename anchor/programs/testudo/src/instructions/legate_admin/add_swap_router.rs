@@ -0,0 +1,58 @@
+use crate::constants::MAX_SWAP_ROUTERS;
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, MaxSwapRoutersReached,
+    SwapRouterAlreadyApproved,
+};
+use anchor_lang::prelude::*;
+
+// Approve a router program that swaps may be executed through
+
+#[derive(Accounts)]
+pub struct AddSwapRouter<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_add_swap_router(ctx: Context<AddSwapRouter>, router: Pubkey) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // check if router is already in the list
+    if legate.approved_swap_routers.contains(&router) {
+        return Err(SwapRouterAlreadyApproved.into());
+    }
+    // Ensure max swap routers is not reached
+    require_gt!(
+        MAX_SWAP_ROUTERS,
+        legate.approved_swap_routers.len(),
+        MaxSwapRoutersReached
+    );
+
+    legate.approved_swap_routers.push(router);
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
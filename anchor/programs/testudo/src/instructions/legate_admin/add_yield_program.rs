@@ -0,0 +1,61 @@
+use crate::constants::MAX_YIELD_PROGRAMS;
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, MaxYieldProgramsReached,
+    YieldProgramAlreadyApproved,
+};
+use anchor_lang::prelude::*;
+
+// Approve a yield adapter program that testudo balances may be deployed into
+
+#[derive(Accounts)]
+pub struct AddYieldProgram<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_add_yield_program(
+    ctx: Context<AddYieldProgram>,
+    yield_program: Pubkey,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // check if the yield program is already in the list
+    if legate.yield_programs.contains(&yield_program) {
+        return Err(YieldProgramAlreadyApproved.into());
+    }
+    // Ensure max yield programs is not reached
+    require_gt!(
+        MAX_YIELD_PROGRAMS,
+        legate.yield_programs.len(),
+        MaxYieldProgramsReached
+    );
+
+    legate.yield_programs.push(yield_program);
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
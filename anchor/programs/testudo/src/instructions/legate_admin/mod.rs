@@ -9,3 +9,37 @@ pub mod update_max_whitelisted_mints;
 pub mod update_treasury;
 
 pub mod update_fee_percent;
+
+pub mod update_mint_fee_percent;
+
+pub mod update_fee_tiers;
+
+pub mod update_mint_fee_limits;
+
+pub mod update_sol_fee_limits;
+
+pub mod update_fee_rounding;
+
+pub mod update_operation_fee;
+
+pub mod add_approved_cpi_program;
+
+pub mod remove_approved_cpi_program;
+
+pub mod add_swap_router;
+
+pub mod remove_swap_router;
+
+pub mod update_mint_price_feed;
+
+pub mod update_oracle_settings;
+
+pub mod update_mint_transfer_hook;
+
+pub mod add_nft_collection;
+
+pub mod remove_nft_collection;
+
+pub mod add_yield_program;
+
+pub mod remove_yield_program;
@@ -0,0 +1,55 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, UnapprovedCpiProgram,
+};
+use anchor_lang::prelude::*;
+
+// Revoke a program's approval for invocation with the Centurion signer
+
+#[derive(Accounts)]
+pub struct RemoveApprovedCpiProgram<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_remove_approved_cpi_program(
+    ctx: Context<RemoveApprovedCpiProgram>,
+    program_id: Pubkey,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // Ensure the program is currently approved
+    require_eq!(
+        legate.approved_cpi_programs.contains(&program_id),
+        true,
+        UnapprovedCpiProgram
+    );
+
+    legate.approved_cpi_programs.retain(|p| *p != program_id);
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, NftCollectionNotApproved,
+};
+use anchor_lang::prelude::*;
+
+// Stop accepting deposits from a collection. NFTs already vaulted can still be withdrawn
+
+#[derive(Accounts)]
+pub struct RemoveNftCollection<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_remove_nft_collection(
+    ctx: Context<RemoveNftCollection>,
+    collection: Pubkey,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // Ensure the collection is currently approved
+    require_eq!(
+        legate.nft_collections.contains(&collection),
+        true,
+        NftCollectionNotApproved
+    );
+
+    legate.nft_collections.retain(|c| *c != collection);
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
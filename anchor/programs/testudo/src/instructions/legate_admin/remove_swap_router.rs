@@ -0,0 +1,52 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, UnapprovedSwapRouter,
+};
+use anchor_lang::prelude::*;
+
+// Revoke a router program so swaps can no longer be executed through it
+
+#[derive(Accounts)]
+pub struct RemoveSwapRouter<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_remove_swap_router(ctx: Context<RemoveSwapRouter>, router: Pubkey) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // Ensure the router is currently approved
+    require_eq!(
+        legate.approved_swap_routers.contains(&router),
+        true,
+        UnapprovedSwapRouter
+    );
+
+    legate.approved_swap_routers.retain(|p| *p != router);
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, UnapprovedYieldProgram,
+};
+use anchor_lang::prelude::*;
+
+// Revoke a yield adapter program so no new positions can be opened in it. Open positions can still be exited
+
+#[derive(Accounts)]
+pub struct RemoveYieldProgram<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_remove_yield_program(
+    ctx: Context<RemoveYieldProgram>,
+    yield_program: Pubkey,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // Ensure the yield program is currently approved
+    require_eq!(
+        legate.yield_programs.contains(&yield_program),
+        true,
+        UnapprovedYieldProgram
+    );
+
+    legate.yield_programs.retain(|p| *p != yield_program);
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::custom_accounts::legate::Legate;
-use crate::errors::ErrorCode::{InvalidAuthority, LegateNotInitialized};
+use crate::errors::ErrorCode::{InvalidAuthority, LegateNotInitialized, LegateNotMigrated};
 use anchor_lang::prelude::*;
 
 /// NEED TO REALLOCATE THE CENTURION ACCOUNTS TO THE NEW MAX TESTUDOS
@@ -19,6 +19,7 @@ pub struct UpdateFeePercent<'info> {
         bump,
         has_one = authority @InvalidAuthority,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 }
@@ -0,0 +1,47 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{InvalidAuthority, LegateNotInitialized, LegateNotMigrated};
+use anchor_lang::prelude::*;
+
+// Choose whether percentage fees round up or down
+
+#[derive(Accounts)]
+pub struct UpdateFeeRounding<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_update_fee_rounding(
+    ctx: Context<UpdateFeeRounding>,
+    round_fees_up: bool,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // update the rounding mode
+    legate.round_fees_up = round_fees_up;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
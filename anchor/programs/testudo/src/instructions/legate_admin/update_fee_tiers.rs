@@ -0,0 +1,71 @@
+use crate::constants::{FEE_PERCENT_DENOMINATOR, MAX_FEE_TIERS};
+use crate::custom_accounts::legate::{FeeTier, FeeTierMode, Legate};
+use crate::errors::ErrorCode::{
+    FeeTiersNotAscending, InvalidAuthority, InvalidFeePercent, LegateNotInitialized,
+    LegateNotMigrated, TooManyFeeTiers,
+};
+use anchor_lang::prelude::*;
+
+// Replace the Legate's amount-tiered fee schedule. An empty schedule restores the flat rate
+
+#[derive(Accounts)]
+pub struct UpdateFeeTiers<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_update_fee_tiers(
+    ctx: Context<UpdateFeeTiers>,
+    new_fee_tiers: Vec<FeeTier>,
+    new_fee_tier_mode: FeeTierMode,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    require_gte!(MAX_FEE_TIERS, new_fee_tiers.len(), TooManyFeeTiers);
+    // Ensure thresholds are strictly ascending so each amount falls in exactly one tier
+    require_eq!(
+        new_fee_tiers
+            .windows(2)
+            .all(|pair| pair[0].threshold < pair[1].threshold),
+        true,
+        FeeTiersNotAscending
+    );
+    // Ensure every tier rate is a valid percentage
+    require_eq!(
+        new_fee_tiers
+            .iter()
+            .all(|tier| tier.percent_for_fees <= FEE_PERCENT_DENOMINATOR),
+        true,
+        InvalidFeePercent
+    );
+
+    // update the fee schedule
+    legate.fee_tiers = new_fee_tiers;
+    legate.fee_tier_mode = new_fee_tier_mode;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
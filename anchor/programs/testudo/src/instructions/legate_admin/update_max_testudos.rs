@@ -1,7 +1,7 @@
 use crate::custom_accounts::legate::Legate;
 use crate::errors::ErrorCode::{
     CannotUpdateMaxTestudosToLessThanCurrentNumberOfTestudos, InvalidAuthority,
-    LegateNotInitialized,
+    LegateNotInitialized, LegateNotMigrated,
 };
 use anchor_lang::prelude::*;
 
@@ -22,6 +22,7 @@ pub struct UpdateMaxTestudos<'info> {
         bump,
         has_one = authority @InvalidAuthority,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 }
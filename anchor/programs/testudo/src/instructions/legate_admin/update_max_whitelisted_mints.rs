@@ -2,7 +2,7 @@ use crate::custom_accounts::legate::Legate;
 use crate::custom_accounts::legate::TestudoTokenWhitelist;
 use crate::errors::ErrorCode::{
     CannotUpdateMaxWhitelistedMintsToLessThanCurrentNumberOfWhitelistedMints, InvalidAuthority,
-    LegateNotInitialized,
+    LegateNotInitialized, LegateNotMigrated,
 };
 use anchor_lang::prelude::*;
 
@@ -22,6 +22,7 @@ pub struct UpdateMaxWhitelistedMints<'info> {
         bump,
         has_one = authority @InvalidAuthority,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
         realloc = 8 + Legate::INIT_SPACE + ((new_max_whitelisted_mints as usize - legate.max_whitelisted_mints as usize) * TestudoTokenWhitelist::INIT_SPACE),
         realloc::payer = authority,
         realloc::zero = false,
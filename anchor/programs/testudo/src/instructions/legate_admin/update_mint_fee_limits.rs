@@ -0,0 +1,57 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, UnsupportedTokenMint,
+};
+use anchor_lang::prelude::*;
+
+// Set the minimum and flat fee (in base units) for a whitelisted mint
+
+#[derive(Accounts)]
+pub struct UpdateMintFeeLimits<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_update_mint_fee_limits(
+    ctx: Context<UpdateMintFeeLimits>,
+    mint: Pubkey,
+    new_min_fee: u64,
+    new_flat_fee: u64,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // update the fee limits on the mint's whitelist entry
+    let whitelisted_mint = legate
+        .testudo_token_whitelist
+        .iter_mut()
+        .find(|t| t.token_mint == mint)
+        .ok_or(UnsupportedTokenMint)?;
+    whitelisted_mint.min_fee = new_min_fee;
+    whitelisted_mint.flat_fee = new_flat_fee;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
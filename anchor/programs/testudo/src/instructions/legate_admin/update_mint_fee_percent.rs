@@ -0,0 +1,61 @@
+use crate::constants::FEE_PERCENT_DENOMINATOR;
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, InvalidFeePercent, LegateNotInitialized, LegateNotMigrated,
+    UnsupportedTokenMint,
+};
+use anchor_lang::prelude::*;
+
+// Set or clear the fee override for a whitelisted mint. None reverts the mint to the Legate's global rate
+
+#[derive(Accounts)]
+pub struct UpdateMintFeePercent<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_update_mint_fee_percent(
+    ctx: Context<UpdateMintFeePercent>,
+    mint: Pubkey,
+    new_fee_percent: Option<u16>, // where 10000 = 100% (decimals are 10000)
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    if let Some(fee_percent) = new_fee_percent {
+        require_gte!(FEE_PERCENT_DENOMINATOR, fee_percent, InvalidFeePercent);
+    }
+
+    // update the fee override on the mint's whitelist entry
+    let whitelisted_mint = legate
+        .testudo_token_whitelist
+        .iter_mut()
+        .find(|t| t.token_mint == mint)
+        .ok_or(UnsupportedTokenMint)?;
+    whitelisted_mint.percent_for_fees = new_fee_percent;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
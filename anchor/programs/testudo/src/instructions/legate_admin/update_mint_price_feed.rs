@@ -0,0 +1,55 @@
+use crate::custom_accounts::legate::{Legate, PriceFeed};
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, UnsupportedTokenMint,
+};
+use anchor_lang::prelude::*;
+
+// Set or clear the oracle account quoting a whitelisted mint in USD
+
+#[derive(Accounts)]
+pub struct UpdateMintPriceFeed<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_update_mint_price_feed(
+    ctx: Context<UpdateMintPriceFeed>,
+    mint: Pubkey,
+    new_price_feed: Option<PriceFeed>,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // update the price feed on the mint's whitelist entry
+    let whitelisted_mint = legate
+        .testudo_token_whitelist
+        .iter_mut()
+        .find(|t| t.token_mint == mint)
+        .ok_or(UnsupportedTokenMint)?;
+    whitelisted_mint.price_feed = new_price_feed;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, LegateNotInitialized, LegateNotMigrated, UnsupportedTokenMint,
+};
+use crate::mint_risk::inspect_mint_risk;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+// Approve the transfer hook program a whitelisted mint currently points at, e.g. after the issuer migrates it
+
+#[derive(Accounts)]
+pub struct UpdateMintTransferHook<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // MINT
+    pub mint: InterfaceAccount<'info, Mint>,
+}
+
+pub fn process_update_mint_transfer_hook(
+    ctx: Context<UpdateMintTransferHook>,
+    new_transfer_hook_program: Option<Pubkey>,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // The mint must still pass screening with the new hook program, and its risk flags are refreshed
+    let risk_flags = inspect_mint_risk(&ctx.accounts.mint, new_transfer_hook_program)?;
+    let whitelisted_mint = legate
+        .testudo_token_whitelist
+        .iter_mut()
+        .find(|t| t.token_mint == ctx.accounts.mint.key())
+        .ok_or(UnsupportedTokenMint)?;
+    whitelisted_mint.transfer_hook_program = new_transfer_hook_program;
+    whitelisted_mint.risk_flags = risk_flags;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
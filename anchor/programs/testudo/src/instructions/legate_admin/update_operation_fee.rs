@@ -0,0 +1,55 @@
+use crate::constants::FEE_PERCENT_DENOMINATOR;
+use crate::custom_accounts::legate::{FeeOperation, Legate, OperationFee};
+use crate::errors::ErrorCode::{
+    InvalidAuthority, InvalidFeePercent, LegateNotInitialized, LegateNotMigrated,
+};
+use anchor_lang::prelude::*;
+
+// Update the fee setting for one type of operation (withdraw, backup recovery, close, swap, internal transfer)
+
+#[derive(Accounts)]
+pub struct UpdateOperationFee<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_update_operation_fee(
+    ctx: Context<UpdateOperationFee>,
+    operation: FeeOperation,
+    new_operation_fee: OperationFee,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    if let Some(fee_percent) = new_operation_fee.percent_for_fees {
+        require_gte!(FEE_PERCENT_DENOMINATOR, fee_percent, InvalidFeePercent);
+    }
+
+    // update the operation's fee setting
+    *legate.operation_fees.get_mut(operation) = new_operation_fee;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use crate::constants::FEE_PERCENT_DENOMINATOR;
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    InvalidAuthority, InvalidFeePercent, LegateNotInitialized, LegateNotMigrated,
+};
+use anchor_lang::prelude::*;
+
+// Update how old, and how uncertain, an oracle price may be before the program refuses to act on it
+
+#[derive(Accounts)]
+pub struct UpdateOracleSettings<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_update_oracle_settings(
+    ctx: Context<UpdateOracleSettings>,
+    new_max_price_staleness_seconds: u64,
+    new_max_price_confidence_bps: u16, // where 10000 = 100%
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    require_gte!(
+        FEE_PERCENT_DENOMINATOR,
+        new_max_price_confidence_bps,
+        InvalidFeePercent
+    );
+
+    legate.max_price_staleness_seconds = new_max_price_staleness_seconds;
+    legate.max_price_confidence_bps = new_max_price_confidence_bps;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{InvalidAuthority, LegateNotInitialized, LegateNotMigrated};
+use anchor_lang::prelude::*;
+
+// Set the minimum and flat fee (in lamports) for SOL and wSOL withdrawals
+
+#[derive(Accounts)]
+pub struct UpdateSolFeeLimits<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE
+    #[account(
+        mut,
+        seeds = [b"legate".as_ref()],
+        bump,
+        has_one = authority @InvalidAuthority,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_update_sol_fee_limits(
+    ctx: Context<UpdateSolFeeLimits>,
+    new_min_fee_lamports: u64,
+    new_flat_fee_lamports: u64,
+) -> Result<()> {
+    let legate = &mut ctx.accounts.legate;
+
+    // double check that the legate account is initialized
+    require_eq!(legate.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legate.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    // update the SOL fee limits
+    legate.min_fee_lamports = new_min_fee_lamports;
+    legate.flat_fee_lamports = new_flat_fee_lamports;
+
+    // update the last updated timestamp
+    legate.last_updated = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -1,5 +1,5 @@
 use crate::custom_accounts::legate::Legate;
-use crate::errors::ErrorCode::{InvalidAuthority, LegateNotInitialized};
+use crate::errors::ErrorCode::{InvalidAuthority, LegateNotInitialized, LegateNotMigrated};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -15,6 +15,7 @@ pub struct UpdateTreasury<'info> {
         bump,
         has_one = authority @InvalidAuthority,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 }
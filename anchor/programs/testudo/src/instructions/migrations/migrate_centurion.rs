@@ -0,0 +1,110 @@
+use crate::constants::CENTURION_VERSION;
+use crate::custom_accounts::centurion::{Centurion, LegacyCenturion};
+use crate::errors::ErrorCode::{
+    AccountAlreadyMigrated, ArithmeticOverflow, CenturionNotInitialized, InvalidAuthority,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+// Bring a Centurion written with the first, unversioned layout up to CENTURION_VERSION. The account is read
+// with the legacy layout, grown to the current size and rewritten. Testudos, balances and settings are kept,
+// the lists added since then start empty and third-party deposits start switched off. Every other Centurion
+// instruction rejects the account until it has been migrated.
+
+#[derive(Accounts)]
+pub struct MigrateCenturion<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // CENTURION. Read by hand, since the legacy layout can't be deserialized as the current one
+    #[account(
+        mut,
+        seeds = [b"centurion", authority.key().as_ref()],
+        bump,
+        owner = crate::ID,
+    )]
+    /// CHECK: Discriminator, initialization and authority are checked in the instruction
+    pub centurion: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_migrate_centurion(ctx: Context<MigrateCenturion>) -> Result<()> {
+    let centurion_info = ctx.accounts.centurion.to_account_info();
+
+    let legacy = {
+        let data = centurion_info.try_borrow_data()?;
+        require_eq!(
+            data.starts_with(Centurion::DISCRIMINATOR),
+            true,
+            CenturionNotInitialized
+        );
+        // Legacy accounts were all allocated at exactly the legacy size, later ones are larger
+        require_eq!(
+            data.len(),
+            8 + LegacyCenturion::INIT_SPACE,
+            AccountAlreadyMigrated
+        );
+        LegacyCenturion::deserialize(&mut &data[8..])
+            .map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize))?
+    };
+
+    // double check that the centurion account is initialized
+    require_eq!(legacy.is_initialized, true, CenturionNotInitialized);
+    // double check that the authority is the same as the centurion authority
+    require_eq!(
+        legacy.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    let centurion = Centurion {
+        authority: legacy.authority,
+        backup_owner: legacy.backup_owner,
+        pubkey_to_password: legacy.pubkey_to_password,
+        bump: legacy.bump,
+        is_initialized: legacy.is_initialized,
+        created_at: legacy.created_at,
+        last_accessed: legacy.last_accessed,
+        lamport_balance: legacy.lamport_balance,
+        testudos: legacy.testudos,
+        version: CENTURION_VERSION,
+        nfts: vec![],
+        stake_accounts: vec![],
+        yield_positions: vec![],
+        accepts_third_party_deposits: false,
+    };
+
+    let new_len = 8 + Centurion::INIT_SPACE;
+
+    // The authority pays the rent for the larger account, so the deposited SOL stays untouched
+    let rent_top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .checked_add(centurion.lamport_balance)
+        .ok_or(ArithmeticOverflow)?
+        .saturating_sub(centurion_info.lamports());
+    if rent_top_up > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: centurion_info.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+    centurion_info.resize(new_len)?;
+
+    let mut data = centurion_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    centurion.try_serialize(&mut writer)?;
+    // Zero whatever the legacy layout left past the end of the new one, such as testudo entries removed by
+    // close_testudo, so the appended fields start empty
+    writer.fill(0);
+
+    Ok(())
+}
@@ -0,0 +1,135 @@
+use crate::constants::{JUPITER_PROGRAM_ID, LEGATE_VERSION};
+use crate::custom_accounts::legate::{
+    FeeTierMode, LegacyLegate, Legate, OperationFees, TestudoTokenWhitelist,
+};
+use crate::errors::ErrorCode::{AccountAlreadyMigrated, InvalidAuthority, LegateNotInitialized};
+use anchor_lang::prelude::*;
+use anchor_lang::system_program::{transfer, Transfer};
+
+// Bring a Legate written with the first, unversioned layout up to LEGATE_VERSION. The account is read with
+// the legacy layout, grown to the current size and rewritten. Settings added since then take the values
+// init_legate gives a new Legate. Existing whitelist entries get no fee override, fee limits, price
+// feed or transfer hook, and no risk flags since they were whitelisted before mints were screened.
+// Every other instruction rejects the Legate until it has been migrated.
+
+#[derive(Accounts)]
+pub struct MigrateLegate<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // LEGATE. Read by hand, since the legacy layout can't be deserialized as the current one
+    #[account(
+        mut,
+        seeds = [b"legate"],
+        bump,
+        owner = crate::ID,
+    )]
+    /// CHECK: Discriminator, initialization and authority are checked in the instruction
+    pub legate: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_migrate_legate(ctx: Context<MigrateLegate>) -> Result<()> {
+    let legate_info = ctx.accounts.legate.to_account_info();
+
+    let legacy = {
+        let data = legate_info.try_borrow_data()?;
+        // An account that already reads as the current layout has nothing to migrate
+        if let Ok(current) = Legate::try_deserialize(&mut &data[..]) {
+            require_neq!(current.version, LEGATE_VERSION, AccountAlreadyMigrated);
+        }
+        require_eq!(
+            data.starts_with(Legate::DISCRIMINATOR),
+            true,
+            LegateNotInitialized
+        );
+        LegacyLegate::deserialize(&mut &data[8..])
+            .map_err(|_| error!(anchor_lang::error::ErrorCode::AccountDidNotDeserialize))?
+    };
+
+    // double check that the legate account is initialized
+    require_eq!(legacy.is_initialized, true, LegateNotInitialized);
+    // double check that the authority is the same as the legate authority
+    require_eq!(
+        legacy.authority,
+        ctx.accounts.authority.key(),
+        InvalidAuthority
+    );
+
+    let legate = Legate {
+        authority: legacy.authority,
+        bump: legacy.bump,
+        is_initialized: legacy.is_initialized,
+        last_updated: Clock::get()?.unix_timestamp as u64,
+        max_centurions_per_user: legacy.max_centurions_per_user,
+        max_testudos_per_user: legacy.max_testudos_per_user,
+        max_whitelisted_mints: legacy.max_whitelisted_mints,
+        treasury_acc: legacy.treasury_acc,
+        percent_for_fees: legacy.percent_for_fees,
+        testudo_token_whitelist: legacy
+            .testudo_token_whitelist
+            .into_iter()
+            .map(|entry| TestudoTokenWhitelist {
+                token_mint: entry.token_mint,
+                token_name: entry.token_name,
+                token_symbol: entry.token_symbol,
+                token_decimals: entry.token_decimals,
+                percent_for_fees: None,
+                min_fee: 0,
+                flat_fee: 0,
+                price_feed: None,
+                transfer_hook_program: None,
+                risk_flags: 0,
+            })
+            .collect(),
+        version: LEGATE_VERSION,
+        // Same starting values as init_legate
+        fee_tier_mode: FeeTierMode::Bracket,
+        fee_tiers: vec![],
+        min_fee_lamports: 0,
+        flat_fee_lamports: 0,
+        round_fees_up: false,
+        operation_fees: OperationFees::initial(),
+        approved_cpi_programs: Legate::initial_approved_cpi_programs(),
+        approved_swap_routers: vec![JUPITER_PROGRAM_ID],
+        max_price_staleness_seconds: 60,
+        max_price_confidence_bps: 200,
+        nft_collections: vec![],
+        yield_programs: vec![],
+    };
+
+    // INIT_SPACE covers 50 whitelist entries, any raised limit needs room for the rest
+    let extra_whitelisted_mints = (legate.max_whitelisted_mints as usize).saturating_sub(50);
+    let new_len =
+        (8 + Legate::INIT_SPACE + extra_whitelisted_mints * TestudoTokenWhitelist::INIT_SPACE)
+            .max(legate_info.data_len());
+
+    // The authority pays the rent for the larger account
+    let rent_top_up = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(legate_info.lamports());
+    if rent_top_up > 0 {
+        transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.authority.to_account_info(),
+                    to: legate_info.clone(),
+                },
+            ),
+            rent_top_up,
+        )?;
+    }
+    legate_info.resize(new_len)?;
+
+    let mut data = legate_info.try_borrow_mut_data()?;
+    let mut writer: &mut [u8] = &mut data[..];
+    legate.try_serialize(&mut writer)?;
+    // Zero whatever the legacy layout left past the end of the new one, so later appended fields start empty
+    writer.fill(0);
+
+    Ok(())
+}
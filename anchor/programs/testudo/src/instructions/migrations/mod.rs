@@ -0,0 +1,2 @@
+pub mod migrate_centurion;
+pub mod migrate_legate;
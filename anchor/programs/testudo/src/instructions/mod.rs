@@ -1,7 +1,12 @@
 pub mod legate_admin;
 pub use legate_admin::{
-    add_mint_testudo::*, update_authority::*, update_fee_percent::*, update_max_testudos::*,
-    update_max_whitelisted_mints::*, update_treasury::*,
+    add_approved_cpi_program::*, add_mint_testudo::*, add_nft_collection::*, add_swap_router::*,
+    add_yield_program::*, remove_approved_cpi_program::*, remove_nft_collection::*,
+    remove_swap_router::*, remove_yield_program::*, update_authority::*, update_fee_percent::*,
+    update_fee_rounding::*, update_fee_tiers::*, update_max_testudos::*,
+    update_max_whitelisted_mints::*, update_mint_fee_limits::*, update_mint_fee_percent::*,
+    update_mint_price_feed::*, update_mint_transfer_hook::*, update_operation_fee::*,
+    update_oracle_settings::*, update_sol_fee_limits::*, update_treasury::*,
 };
 
 pub mod account_management;
@@ -11,15 +16,54 @@ pub use account_management::{
 };
 
 pub mod deposit;
-pub use deposit::{deposit_sol::*, deposit_spl::*};
+pub use deposit::{
+    deposit_sol::*, deposit_sol_for::*, deposit_sol_to_wsol::*, deposit_spl::*, deposit_spl_for::*,
+};
 
 pub mod withdraw;
 pub use withdraw::{
-    withdraw_sol::*, withdraw_sol_to_backup::*, withdraw_spl::*, withdraw_spl_to_backup::*,
+    rescue_tokens::*, withdraw_sol::*, withdraw_sol_to_backup::*, withdraw_spl::*,
+    withdraw_spl_to_backup::*,
 };
 
 pub mod centurion_config;
-pub use centurion_config::update_back_up_account::*;
+pub use centurion_config::{
+    reconcile_centurion::*, set_third_party_deposits::*, update_back_up_account::*,
+};
 
 pub mod swaps;
 pub use swaps::swap::*;
+
+pub mod dca;
+pub use dca::{cancel_dca_order::*, create_dca_order::*, execute_dca_cycle::*};
+
+pub mod conditional_orders;
+pub use conditional_orders::{
+    cancel_conditional_order::*, create_conditional_order::*, execute_conditional_order::*,
+};
+
+pub mod rebalance;
+pub use rebalance::{close_rebalance_policy::*, execute_rebalance::*, set_rebalance_policy::*};
+
+pub mod views;
+pub use views::{quote_fee::*, quote_sol_balance::*, quote_usd_value::*, quote_yield_position::*};
+
+pub mod native_sol;
+pub use native_sol::{unwrap_sol::*, wrap_sol::*};
+
+pub mod nfts;
+pub use nfts::{deposit_nft::*, withdraw_nft::*, withdraw_nft_to_backup::*};
+
+pub mod staking;
+pub use staking::{
+    create_stake_account::*, deactivate_stake::*, delegate_stake::*, withdraw_stake::*,
+    withdraw_stake_to_backup::*,
+};
+
+pub mod yield_positions;
+pub use yield_positions::{
+    enter_yield_position::*, exit_yield_position::*, withdraw_yield_position_to_backup::*,
+};
+
+pub mod migrations;
+pub use migrations::{migrate_centurion::*, migrate_legate::*};
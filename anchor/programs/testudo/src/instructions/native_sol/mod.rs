@@ -0,0 +1,3 @@
+pub mod wrap_sol;
+
+pub mod unwrap_sol;
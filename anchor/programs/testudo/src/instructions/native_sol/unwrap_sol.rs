@@ -0,0 +1,166 @@
+use crate::constants::WSOL_MINT;
+use crate::custom_accounts::centurion::Centurion;
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds, InvalidAuthority,
+    InvalidPasswordSignature, InvalidTokenMint, InvalidTreasuryAccount, LegateNotInitialized,
+    LegateNotMigrated,
+};
+use crate::events::SolUnwrapped;
+use crate::instructions::swaps::native_sol::{
+    charge_internal_transfer_fee, unwrap_centurion_sol, NATIVE_SOL_SEED,
+};
+use crate::transfers::transfer_tokens;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Move wSOL from the Centurion's wSOL testudo back into `lamport_balance`. wSOL accounts can only be unwrapped
+// by closing them, so the amount goes through the temporary wSOL account used by swaps, which is closed again.
+// The internal transfer fee is then taken from the unwrapped SOL, so the password must sign.
+
+#[derive(Accounts)]
+pub struct UnwrapSol<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // WSOL TESTUDO
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+        seeds = [centurion.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // TEMPORARY WSOL ACCOUNT. Closed into the Centurion before the instruction ends
+    #[account(
+        init,
+        payer = authority,
+        token::mint = mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+        seeds = [centurion.key().as_ref(), mint.key().as_ref(), NATIVE_SOL_SEED],
+        bump,
+    )]
+    pub native_sol_account: InterfaceAccount<'info, TokenAccount>,
+
+    // MINT
+    #[account(
+        address = WSOL_MINT @InvalidTokenMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // TREASURY. Receives the internal transfer fee in lamports
+    #[account(
+        mut,
+        constraint = legate.treasury_acc == treasury.key() @InvalidTreasuryAccount
+    )]
+    /// CHECK: Explicit wrapper for AccountInfo type to emphasize that no checks are performed
+    pub treasury: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_unwrap_sol(ctx: Context<UnwrapSol>, amount_in_lamports: u64) -> Result<()> {
+    // Validate the pubkey matches the password's pubkey
+    require_eq!(
+        ctx.accounts.centurion.pubkey_to_password,
+        ctx.accounts.valid_signer_of_password.key(),
+        InvalidPasswordSignature
+    );
+
+    // Ensure the wSOL testudo is registered with the Centurion and holds enough
+    require_eq!(
+        ctx.accounts
+            .centurion
+            .testudos
+            .iter()
+            .any(|testudo| testudo.token_mint == WSOL_MINT),
+        true,
+        InvalidTokenMint
+    );
+    require_gte!(
+        ctx.accounts.testudo.amount,
+        amount_in_lamports,
+        InsufficientFunds
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.testudo.to_account_info(),
+        ctx.accounts.native_sol_account.to_account_info(),
+        ctx.accounts.centurion.to_account_info(),
+        &ctx.accounts.mint,
+        amount_in_lamports,
+        signer_seeds,
+        &[],
+    )?;
+    ctx.accounts.native_sol_account.reload()?;
+
+    // The authority paid the temporary account's rent and gets it back
+    let authority_info = ctx.accounts.authority.to_account_info();
+    let unwrapped = unwrap_centurion_sol(
+        &mut ctx.accounts.centurion,
+        &ctx.accounts.native_sol_account,
+        &authority_info,
+        &ctx.accounts.token_program,
+        signer_seeds,
+    )?;
+
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let fee = charge_internal_transfer_fee(
+        &mut ctx.accounts.centurion,
+        &treasury_info,
+        &ctx.accounts.legate,
+        unwrapped,
+    )?;
+
+    emit!(SolUnwrapped {
+        centurion: ctx.accounts.centurion.key(),
+        amount: unwrapped,
+        protocol_fee: fee,
+    });
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
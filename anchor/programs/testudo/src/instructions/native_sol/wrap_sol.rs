@@ -0,0 +1,127 @@
+use crate::constants::WSOL_MINT;
+use crate::custom_accounts::centurion::Centurion;
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority,
+    InvalidPasswordSignature, InvalidTokenMint, InvalidTreasuryAccount, LegateNotInitialized,
+    LegateNotMigrated,
+};
+use crate::events::SolWrapped;
+use crate::instructions::swaps::native_sol::{charge_internal_transfer_fee, wrap_centurion_sol};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Move SOL tracked in `lamport_balance` into the Centurion's wSOL testudo. Only the internal transfer fee,
+// taken from the amount, leaves the Centurion. Since that fee moves funds, the password must sign.
+
+#[derive(Accounts)]
+pub struct WrapSol<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // WSOL TESTUDO
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+        seeds = [centurion.key().as_ref(), mint.key().as_ref()],
+        bump,
+    )]
+    pub testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // MINT
+    #[account(
+        address = WSOL_MINT @InvalidTokenMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // TREASURY. Receives the internal transfer fee in lamports
+    #[account(
+        mut,
+        constraint = legate.treasury_acc == treasury.key() @InvalidTreasuryAccount
+    )]
+    /// CHECK: Explicit wrapper for AccountInfo type to emphasize that no checks are performed
+    pub treasury: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn process_wrap_sol(ctx: Context<WrapSol>, amount_in_lamports: u64) -> Result<()> {
+    // Validate the pubkey matches the password's pubkey
+    require_eq!(
+        ctx.accounts.centurion.pubkey_to_password,
+        ctx.accounts.valid_signer_of_password.key(),
+        InvalidPasswordSignature
+    );
+
+    let centurion = &mut ctx.accounts.centurion;
+
+    // Ensure the wSOL testudo is registered with the Centurion
+    require_eq!(
+        centurion
+            .testudos
+            .iter()
+            .any(|testudo| testudo.token_mint == WSOL_MINT),
+        true,
+        InvalidTokenMint
+    );
+
+    let treasury_info = ctx.accounts.treasury.to_account_info();
+    let fee = charge_internal_transfer_fee(
+        centurion,
+        &treasury_info,
+        &ctx.accounts.legate,
+        amount_in_lamports,
+    )?;
+    let amount_after_fee = amount_in_lamports
+        .checked_sub(fee)
+        .ok_or(ArithmeticOverflow)?;
+
+    wrap_centurion_sol(
+        centurion,
+        &ctx.accounts.testudo,
+        &ctx.accounts.token_program,
+        amount_after_fee,
+    )?;
+
+    emit!(SolWrapped {
+        centurion: centurion.key(),
+        amount: amount_after_fee,
+        protocol_fee: fee,
+    });
+
+    centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
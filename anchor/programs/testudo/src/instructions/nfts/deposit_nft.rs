@@ -0,0 +1,146 @@
+use crate::constants::{MAX_NFTS_PER_CENTURION, TOKEN_METADATA_PROGRAM_ID};
+use crate::custom_accounts::{centurion::Centurion, legate::Legate};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds, InvalidAuthority,
+    InvalidNftAmount, InvalidNftMint, LegateNotInitialized, LegateNotMigrated, MaxNftsReached,
+    NftCollectionNotApproved,
+};
+use crate::events::NftDeposited;
+use crate::metadata::verified_collection;
+use crate::mint_risk::inspect_mint_risk;
+use crate::transfers::{require_approved_transfer_hook, transfer_tokens};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Deposit an NFT, or an amount of a semi-fungible token, from a verified collection approved by the Legate.
+
+#[derive(Accounts)]
+pub struct DepositNft<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    // AUTHORITY TOKEN ACCOUNT
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = authority,
+        token::token_program = token_program,
+    )]
+    pub authority_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // NFT VAULT
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+        seeds = [centurion.key().as_ref(), mint.key().as_ref(), b"nft"],
+        bump,
+    )]
+    pub nft_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // MINT
+    #[account(
+        mint::token_program = token_program,
+        constraint = mint.decimals == 0 @InvalidNftMint,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // METADATA
+    #[account(
+        seeds = [b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), mint.key().as_ref()],
+        seeds::program = TOKEN_METADATA_PROGRAM_ID,
+        bump,
+    )]
+    /// CHECK: Token Metadata account of the mint, decoded by `verified_collection`
+    pub metadata: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID || token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = system_program.key() == anchor_lang::system_program::ID,
+    )]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_deposit_nft<'info>(
+    ctx: Context<'_, '_, '_, 'info, DepositNft<'info>>,
+    amount: u64,
+) -> Result<()> {
+    let mint_key = ctx.accounts.mint.key();
+
+    // The collection must be verified on the NFT and approved by the Legate
+    let collection =
+        verified_collection(&ctx.accounts.metadata, &mint_key)?.ok_or(NftCollectionNotApproved)?;
+    require_eq!(
+        ctx.accounts.legate.nft_collections.contains(&collection),
+        true,
+        NftCollectionNotApproved
+    );
+
+    // Token-2022 NFTs are screened like whitelisted mints. NFT mints have no whitelist entry, so a hooked
+    // mint is always rejected, while milder powers such as a freeze authority are expected on NFTs
+    inspect_mint_risk(&ctx.accounts.mint, None)?;
+    require_approved_transfer_hook(&ctx.accounts.legate, &ctx.accounts.mint)?;
+
+    require_gt!(amount, 0, InvalidNftAmount);
+    require_gte!(
+        ctx.accounts.authority_token_account.amount,
+        amount,
+        InsufficientFunds
+    );
+
+    // Vaulted NFTs are listed apart from testudos and don't count toward the testudo limit
+    let centurion = &mut ctx.accounts.centurion;
+    if !centurion.nfts.contains(&mint_key) {
+        require_gt!(MAX_NFTS_PER_CENTURION, centurion.nfts.len(), MaxNftsReached);
+        centurion.nfts.push(mint_key);
+    }
+
+    transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.authority_token_account.to_account_info(),
+        ctx.accounts.nft_vault.to_account_info(),
+        ctx.accounts.authority.to_account_info(),
+        &ctx.accounts.mint,
+        amount,
+        &[],
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(NftDeposited {
+        centurion: ctx.accounts.centurion.key(),
+        mint: mint_key,
+        collection,
+        amount,
+    });
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -0,0 +1,7 @@
+pub mod vault;
+
+pub mod deposit_nft;
+
+pub mod withdraw_nft;
+
+pub mod withdraw_nft_to_backup;
@@ -0,0 +1,43 @@
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::NftNotVaulted;
+use crate::transfers::harvest_withheld_fees;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+
+// NFT vaults are Centurion-owned token accounts at [centurion, mint, "nft"], one per NFT or semi-fungible mint.
+// They are listed in `centurion.nfts` rather than `centurion.testudos`.
+
+// Close an emptied vault, returning its rent to `rent_recipient`, and drop the mint from the Centurion.
+// `mint` must be writable, since transfer fees withheld in the vault are harvested into it first.
+pub fn close_nft_vault<'info>(
+    centurion: &mut Account<'info, Centurion>,
+    nft_vault: &InterfaceAccount<'info, TokenAccount>,
+    mint: &InterfaceAccount<'info, Mint>,
+    rent_recipient: AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    // Fees withheld from earlier incoming transfers would block the close
+    harvest_withheld_fees(
+        token_program.to_account_info(),
+        mint,
+        nft_vault.to_account_info(),
+    )?;
+
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: nft_vault.to_account_info(),
+            destination: rent_recipient,
+            authority: centurion.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    let vaulted_count = centurion.nfts.len();
+    centurion.nfts.retain(|nft_mint| *nft_mint != mint.key());
+    require_gt!(vaulted_count, centurion.nfts.len(), NftNotVaulted);
+    Ok(())
+}
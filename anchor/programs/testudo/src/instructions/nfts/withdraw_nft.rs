@@ -0,0 +1,127 @@
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds, InvalidAuthority,
+    InvalidNftAmount, InvalidPasswordSignature, NftNotVaulted,
+};
+use crate::events::NftWithdrawn;
+use crate::instructions::nfts::vault::close_nft_vault;
+use crate::transfers::transfer_tokens;
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Withdraw a vaulted NFT, or part of a semi-fungible balance, to the authority. An emptied vault is closed.
+
+#[derive(Accounts)]
+pub struct WithdrawNft<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+        constraint = centurion.nfts.contains(&mint.key()) @NftNotVaulted,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // NFT VAULT
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+        seeds = [centurion.key().as_ref(), mint.key().as_ref(), b"nft"],
+        bump,
+    )]
+    pub nft_vault: InterfaceAccount<'info, TokenAccount>,
+
+    // AUTHORITY ATA
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program,
+    )]
+    pub authority_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // MINT. Writable so withheld transfer fees can be harvested into it when the vault is closed
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // PROGRAMS
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID || token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = associated_token_program.key() == anchor_spl::associated_token::ID,
+    )]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        constraint = system_program.key() == anchor_lang::system_program::ID,
+    )]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_withdraw_nft<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawNft<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require_gt!(amount, 0, InvalidNftAmount);
+    require_gte!(ctx.accounts.nft_vault.amount, amount, InsufficientFunds);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.nft_vault.to_account_info(),
+        ctx.accounts.authority_ata.to_account_info(),
+        ctx.accounts.centurion.to_account_info(),
+        &ctx.accounts.mint,
+        amount,
+        signer_seeds,
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(NftWithdrawn {
+        centurion: ctx.accounts.centurion.key(),
+        mint: ctx.accounts.mint.key(),
+        recipient: ctx.accounts.authority_ata.key(),
+        amount,
+    });
+
+    ctx.accounts.nft_vault.reload()?;
+    if ctx.accounts.nft_vault.amount == 0 {
+        close_nft_vault(
+            &mut ctx.accounts.centurion,
+            &ctx.accounts.nft_vault,
+            &ctx.accounts.mint,
+            ctx.accounts.authority.to_account_info(),
+            &ctx.accounts.token_program,
+            signer_seeds,
+        )?;
+    }
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
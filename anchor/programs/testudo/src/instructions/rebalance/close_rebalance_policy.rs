@@ -0,0 +1,54 @@
+use crate::custom_accounts::{centurion::Centurion, rebalance_policy::RebalancePolicy};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
+};
+use anchor_lang::prelude::*;
+
+// Remove the rebalance policy of a Centurion, stopping keeper rebalances. The rent is returned to the authority.
+
+#[derive(Accounts)]
+pub struct CloseRebalancePolicy<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // REBALANCE POLICY
+    #[account(
+        mut,
+        close = authority,
+        seeds = [b"rebalance_policy".as_ref(), centurion.key().as_ref()],
+        bump = rebalance_policy.bump,
+        has_one = authority @InvalidAuthority,
+        has_one = centurion @InvalidAuthority,
+    )]
+    pub rebalance_policy: Account<'info, RebalancePolicy>,
+}
+
+pub fn process_close_rebalance_policy(ctx: Context<CloseRebalancePolicy>) -> Result<()> {
+    require_eq!(
+        ctx.accounts.centurion.pubkey_to_password,
+        ctx.accounts.valid_signer_of_password.key(),
+        InvalidPasswordSignature
+    );
+
+    msg!(
+        "Closed rebalance policy of centurion {}",
+        ctx.accounts.centurion.key()
+    );
+    Ok(())
+}
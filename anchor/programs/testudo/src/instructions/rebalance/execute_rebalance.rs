@@ -0,0 +1,313 @@
+use crate::constants::{FEE_PERCENT_DENOMINATOR, REBALANCE_ACCOUNTS_PER_TARGET};
+use crate::custom_accounts::{
+    centurion::Centurion, legate::Legate, rebalance_policy::RebalancePolicy,
+};
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority,
+    InvalidPasswordSignature, InvalidRebalanceLeg, InvalidTreasuryAccount, LegateNotInitialized,
+    LegateNotMigrated, OrderTestudoNotRegistered, RebalanceNotNeeded, RebalanceTooSoon,
+    SlippageBudgetExceeded, UnapprovedSwapRouter,
+};
+use crate::events::RebalanceExecuted;
+use crate::instructions::swaps::execute::{execute_swap, SwapExecution};
+use crate::instructions::swaps::router::RouterInstructionWithIdxs;
+use crate::oracle::{mint_price_usd, usd_value};
+use anchor_lang::prelude::*;
+use anchor_spl::{
+    associated_token::get_associated_token_address_with_program_id,
+    token_interface::{Mint, TokenAccount, TokenInterface},
+};
+
+// Move a Centurion's testudos back towards the weights of its rebalance policy, in one transaction.
+// The owner may rebalance at any time with their password; any keeper may once the drift threshold and
+// interval are reached. Drift and the slippage budget are measured in USD from the Legate's price feeds.
+//
+// Remaining accounts: REBALANCE_ACCOUNTS_PER_TARGET accounts for each policy target, in policy order
+// (testudo, mint, price account, treasury ATA, token program), followed by the router accounts of every leg.
+
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct RebalanceLeg {
+    pub source_index: u8,      // Policy target sold
+    pub destination_index: u8, // Policy target bought
+    pub amount_in: u64,        // Maximum source decrease
+    pub router_swap: RouterInstructionWithIdxs,
+    pub router_setup: Vec<RouterInstructionWithIdxs>,
+    pub router_cleanup: Option<RouterInstructionWithIdxs>,
+}
+
+#[derive(Accounts)]
+pub struct Rebalance<'info> {
+    // SIGNERS. The password is only required to skip the keeper threshold and interval
+    #[account(mut)]
+    pub executor: Signer<'info>,
+    pub valid_signer_of_password: Option<Signer<'info>>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), rebalance_policy.authority.as_ref()],
+        bump = centurion.bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // REBALANCE POLICY
+    #[account(
+        mut,
+        seeds = [b"rebalance_policy".as_ref(), centurion.key().as_ref()],
+        bump = rebalance_policy.bump,
+        has_one = centurion @InvalidAuthority,
+    )]
+    pub rebalance_policy: Account<'info, RebalancePolicy>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // PROGRAMS
+    /// CHECK: Any router program approved by the Legate (Jupiter, an AMM, ...)
+    #[account(
+        executable,
+        constraint = legate.approved_swap_routers.contains(&swap_router.key()) @UnapprovedSwapRouter
+    )]
+    pub swap_router: UncheckedAccount<'info>,
+}
+
+pub fn process_rebalance<'info>(
+    ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+    legs: Vec<RebalanceLeg>,
+) -> Result<()> {
+    let policy = &ctx.accounts.rebalance_policy;
+    let targets = policy.targets.clone();
+    let authority_key = policy.authority;
+    require_gt!(legs.len(), 0, InvalidRebalanceLeg);
+
+    let target_accounts_len = targets.len() * REBALANCE_ACCOUNTS_PER_TARGET;
+    require_gte!(
+        ctx.remaining_accounts.len(),
+        target_accounts_len,
+        InvalidRebalanceLeg
+    );
+    let (target_accounts, router_accounts) = ctx.remaining_accounts.split_at(target_accounts_len);
+
+    // The owner approves with their password; anyone else is a keeper
+    let centurion = &ctx.accounts.centurion;
+    let owner_approved = match &ctx.accounts.valid_signer_of_password {
+        Some(valid_signer_of_password) => {
+            require_keys_eq!(
+                valid_signer_of_password.key(),
+                centurion.pubkey_to_password,
+                InvalidPasswordSignature
+            );
+            require_keys_eq!(
+                ctx.accounts.executor.key(),
+                centurion.authority,
+                InvalidAuthority
+            );
+            true
+        }
+        None => false,
+    };
+
+    // Check every target's accounts and value its testudo at the oracle price
+    let legate = &ctx.accounts.legate;
+    let mut prices = Vec::with_capacity(targets.len());
+    let mut decimals = Vec::with_capacity(targets.len());
+    let mut values = Vec::with_capacity(targets.len());
+    for (idx, target) in targets.iter().enumerate() {
+        let accounts = &target_accounts
+            [idx * REBALANCE_ACCOUNTS_PER_TARGET..(idx + 1) * REBALANCE_ACCOUNTS_PER_TARGET];
+        let (testudo_info, mint_info, price_info, treasury_ata_info, token_program_info) = (
+            &accounts[0],
+            &accounts[1],
+            &accounts[2],
+            &accounts[3],
+            &accounts[4],
+        );
+
+        require_keys_eq!(mint_info.key(), target.mint, InvalidRebalanceLeg);
+        require_eq!(
+            centurion
+                .testudos
+                .iter()
+                .any(|t| t.token_mint == target.mint && t.testudo_pubkey == testudo_info.key()),
+            true,
+            OrderTestudoNotRegistered
+        );
+        require_keys_eq!(
+            *mint_info.owner,
+            token_program_info.key(),
+            InvalidRebalanceLeg
+        );
+        require_keys_eq!(
+            treasury_ata_info.key(),
+            get_associated_token_address_with_program_id(
+                &legate.treasury_acc,
+                &target.mint,
+                token_program_info.key
+            ),
+            InvalidTreasuryAccount
+        );
+
+        let mint = InterfaceAccount::<Mint>::try_from(mint_info)?;
+        let testudo = InterfaceAccount::<TokenAccount>::try_from(testudo_info)?;
+        let price = mint_price_usd(legate, &target.mint, price_info)?;
+        values.push(usd_value(testudo.amount, mint.decimals, price)? as u128);
+        prices.push(price);
+        decimals.push(mint.decimals);
+    }
+
+    // Drift of each target from its weight, in micro-USD. Positive means overweight
+    let total_value: u128 = values.iter().sum();
+    require_gt!(total_value, 0, RebalanceNotNeeded);
+    let mut drifts = Vec::with_capacity(targets.len());
+    let mut max_drift_bps: u128 = 0;
+    for (target, value) in targets.iter().zip(values.iter()) {
+        let target_value =
+            total_value * target.weight_bps as u128 / FEE_PERCENT_DENOMINATOR as u128;
+        let drift = *value as i128 - target_value as i128;
+        max_drift_bps =
+            max_drift_bps.max(drift.unsigned_abs() * FEE_PERCENT_DENOMINATOR as u128 / total_value);
+        drifts.push(drift);
+    }
+    let max_drift_bps = max_drift_bps as u16;
+    msg!(
+        "Portfolio value: {} micro-USD, max drift: {} bps",
+        total_value,
+        max_drift_bps
+    );
+
+    // Keepers may only rebalance a portfolio that has drifted far enough, at most once per interval
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    if !owner_approved {
+        require_gte!(
+            current_time,
+            policy
+                .last_rebalanced_at
+                .checked_add(policy.min_interval_seconds)
+                .ok_or(ArithmeticOverflow)?,
+            RebalanceTooSoon
+        );
+        require_gte!(
+            max_drift_bps,
+            policy.drift_threshold_bps,
+            RebalanceNotNeeded
+        );
+    }
+    let max_slippage_bps = policy.max_slippage_bps;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        authority_key.as_ref(),
+        &[ctx.accounts.centurion.bump],
+    ]];
+
+    let leg_count = legs.len() as u8;
+    let mut value_in_total: u128 = 0;
+    let mut value_out_total: u128 = 0;
+    for leg in legs {
+        let source_index = leg.source_index as usize;
+        let destination_index = leg.destination_index as usize;
+        require_gt!(targets.len(), source_index, InvalidRebalanceLeg);
+        require_gt!(targets.len(), destination_index, InvalidRebalanceLeg);
+        require_neq!(source_index, destination_index, InvalidRebalanceLeg);
+
+        // Only sell overweight testudos into underweight ones
+        require_gt!(drifts[source_index], 0, InvalidRebalanceLeg);
+        require_gt!(0, drifts[destination_index], InvalidRebalanceLeg);
+
+        let source_accounts = &target_accounts[source_index * REBALANCE_ACCOUNTS_PER_TARGET..];
+        let destination_accounts =
+            &target_accounts[destination_index * REBALANCE_ACCOUNTS_PER_TARGET..];
+        let mut source_testudo = InterfaceAccount::<TokenAccount>::try_from(&source_accounts[0])?;
+        let mut destination_testudo =
+            InterfaceAccount::<TokenAccount>::try_from(&destination_accounts[0])?;
+        let source_mint = InterfaceAccount::<Mint>::try_from(&source_accounts[1])?;
+        let destination_mint = InterfaceAccount::<Mint>::try_from(&destination_accounts[1])?;
+        let treasury_destination_ata =
+            InterfaceAccount::<TokenAccount>::try_from(&destination_accounts[3])?;
+        let source_token_program = Interface::<TokenInterface>::try_from(&source_accounts[4])?;
+        let destination_token_program =
+            Interface::<TokenInterface>::try_from(&destination_accounts[4])?;
+
+        // The slippage budget is enforced over the whole rebalance, not per leg
+        let outcome = execute_swap(
+            SwapExecution {
+                centurion: &mut ctx.accounts.centurion,
+                legate: &ctx.accounts.legate,
+                source_testudo: &mut source_testudo,
+                destination_testudo: &mut destination_testudo,
+                source_mint: &source_mint,
+                destination_mint: &destination_mint,
+                treasury_destination_ata: &treasury_destination_ata,
+                source_token_program: &source_token_program,
+                destination_token_program: &destination_token_program,
+                swap_router: ctx.accounts.swap_router.key(),
+                rent_recipient: ctx.accounts.executor.to_account_info(),
+                min_amount_out: 0,
+                max_amount_in: leg.amount_in,
+            },
+            router_accounts,
+            leg.router_swap,
+            leg.router_setup,
+            leg.router_cleanup,
+            signer_seeds,
+        )?;
+
+        let value_in = usd_value(
+            outcome.amount_in,
+            decimals[source_index],
+            prices[source_index],
+        )? as u128;
+        let value_out = usd_value(
+            outcome.amount_out,
+            decimals[destination_index],
+            prices[destination_index],
+        )? as u128;
+
+        // Never push a testudo past its target weight
+        drifts[source_index] -= value_in as i128;
+        drifts[destination_index] += value_in as i128;
+        require_gte!(drifts[source_index], 0, InvalidRebalanceLeg);
+        require_gte!(0, drifts[destination_index], InvalidRebalanceLeg);
+
+        value_in_total = value_in_total
+            .checked_add(value_in)
+            .ok_or(ArithmeticOverflow)?;
+        value_out_total = value_out_total
+            .checked_add(value_out)
+            .ok_or(ArithmeticOverflow)?;
+    }
+
+    // Protocol fees are a known cost and are not counted against the slippage budget
+    require_gte!(
+        value_out_total * FEE_PERCENT_DENOMINATOR as u128,
+        value_in_total * (FEE_PERCENT_DENOMINATOR - max_slippage_bps) as u128,
+        SlippageBudgetExceeded
+    );
+    msg!(
+        "Rebalance sold {} micro-USD for {} micro-USD",
+        value_in_total,
+        value_out_total
+    );
+
+    ctx.accounts.rebalance_policy.last_rebalanced_at = current_time;
+
+    emit!(RebalanceExecuted {
+        rebalance_policy: ctx.accounts.rebalance_policy.key(),
+        centurion: ctx.accounts.centurion.key(),
+        executor: ctx.accounts.executor.key(),
+        legs: leg_count,
+        max_drift_bps,
+        value_in_usd: u64::try_from(value_in_total).map_err(|_| ArithmeticOverflow)?,
+        value_out_usd: u64::try_from(value_out_total).map_err(|_| ArithmeticOverflow)?,
+    });
+
+    Ok(())
+}
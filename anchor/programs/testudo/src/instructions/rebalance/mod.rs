@@ -0,0 +1,5 @@
+pub mod set_rebalance_policy;
+
+pub mod close_rebalance_policy;
+
+pub mod execute_rebalance;
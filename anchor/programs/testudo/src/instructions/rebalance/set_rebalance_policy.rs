@@ -0,0 +1,136 @@
+use crate::constants::{FEE_PERCENT_DENOMINATOR, MAX_REBALANCE_TARGETS, WSOL_MINT};
+use crate::custom_accounts::{
+    centurion::Centurion,
+    legate::Legate,
+    rebalance_policy::{RebalancePolicy, RebalanceTarget},
+};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
+    InvalidRebalancePolicy, LegateNotInitialized, LegateNotMigrated, MissingPriceFeed,
+    OrderTestudoNotRegistered,
+};
+use anchor_lang::prelude::*;
+
+// Create or replace the rebalance policy of a Centurion. Every target must be a registered testudo whose
+// mint has a price feed on the Legate, since drift is measured in USD.
+
+#[derive(Accounts)]
+pub struct SetRebalancePolicy<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // REBALANCE POLICY
+    #[account(
+        init_if_needed,
+        payer = authority,
+        space = 8 + RebalancePolicy::INIT_SPACE,
+        seeds = [b"rebalance_policy".as_ref(), centurion.key().as_ref()],
+        bump,
+    )]
+    pub rebalance_policy: Account<'info, RebalancePolicy>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // PROGRAMS
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_set_rebalance_policy(
+    ctx: Context<SetRebalancePolicy>,
+    targets: Vec<RebalanceTarget>,
+    drift_threshold_bps: u16,
+    max_slippage_bps: u16,
+    min_interval_seconds: u64,
+) -> Result<()> {
+    require_eq!(
+        ctx.accounts.centurion.pubkey_to_password,
+        ctx.accounts.valid_signer_of_password.key(),
+        InvalidPasswordSignature
+    );
+
+    // At least two targets, each used once, with weights adding up to 100%
+    require_gte!(targets.len(), 2, InvalidRebalancePolicy);
+    require_gte!(MAX_REBALANCE_TARGETS, targets.len(), InvalidRebalancePolicy);
+    let total_weight: u32 = targets.iter().map(|t| t.weight_bps as u32).sum();
+    require_eq!(
+        total_weight,
+        FEE_PERCENT_DENOMINATOR as u32,
+        InvalidRebalancePolicy
+    );
+    for (idx, target) in targets.iter().enumerate() {
+        require_eq!(
+            targets[..idx].iter().any(|t| t.mint == target.mint),
+            false,
+            InvalidRebalancePolicy
+        );
+    }
+    require_gt!(drift_threshold_bps, 0, InvalidRebalancePolicy);
+    require_gte!(
+        FEE_PERCENT_DENOMINATOR,
+        drift_threshold_bps,
+        InvalidRebalancePolicy
+    );
+    require_gte!(
+        FEE_PERCENT_DENOMINATOR,
+        max_slippage_bps,
+        InvalidRebalancePolicy
+    );
+
+    // Targets are testudos priced by the Legate. The Centurion's own SOL is not a testudo and is left out
+    let centurion = &ctx.accounts.centurion;
+    let legate = &ctx.accounts.legate;
+    for target in targets.iter() {
+        require_keys_neq!(target.mint, WSOL_MINT, InvalidRebalancePolicy);
+        require_eq!(
+            centurion
+                .testudos
+                .iter()
+                .any(|t| t.token_mint == target.mint),
+            true,
+            OrderTestudoNotRegistered
+        );
+        require_eq!(
+            legate.price_feed_for_mint(&target.mint).is_some(),
+            true,
+            MissingPriceFeed
+        );
+    }
+
+    let current_time = Clock::get()?.unix_timestamp as u64;
+    let rebalance_policy = &mut ctx.accounts.rebalance_policy;
+    if rebalance_policy.created_at == 0 {
+        rebalance_policy.created_at = current_time;
+    }
+    rebalance_policy.authority = ctx.accounts.authority.key();
+    rebalance_policy.centurion = ctx.accounts.centurion.key();
+    rebalance_policy.bump = ctx.bumps.rebalance_policy;
+    rebalance_policy.targets = targets;
+    rebalance_policy.drift_threshold_bps = drift_threshold_bps;
+    rebalance_policy.max_slippage_bps = max_slippage_bps;
+    rebalance_policy.min_interval_seconds = min_interval_seconds;
+
+    Ok(())
+}
@@ -0,0 +1,123 @@
+use crate::constants::MAX_STAKE_ACCOUNTS_PER_CENTURION;
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds,
+    InvalidAuthority, InvalidPasswordSignature, InvalidStakeAmount, MaxStakeAccountsReached,
+};
+use crate::events::StakeAccountCreated;
+use anchor_lang::prelude::*;
+use solana_stake_interface::{
+    instruction::initialize,
+    program::ID as STAKE_PROGRAM_ID,
+    state::{Authorized, Lockup, StakeStateV2},
+};
+
+// Create a stake account funded from the Centurion's lamport_balance, with the Centurion as staker and withdrawer.
+// The authority pays the stake account's rent, which comes back to the Centurion when the account is withdrawn.
+
+#[derive(Accounts)]
+#[instruction(stake_index: u8)]
+pub struct CreateStakeAccount<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // STAKE ACCOUNT
+    #[account(
+        init,
+        payer = authority,
+        space = StakeStateV2::size_of(),
+        owner = STAKE_PROGRAM_ID,
+        seeds = [centurion.key().as_ref(), b"stake".as_ref(), &[stake_index]],
+        bump,
+    )]
+    /// CHECK: Created here and initialised by the stake program
+    pub stake_account: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    #[account(address = STAKE_PROGRAM_ID)]
+    /// CHECK: Stake program
+    pub stake_program: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+pub fn process_create_stake_account(
+    ctx: Context<CreateStakeAccount>,
+    _stake_index: u8,
+    amount_in_lamports: u64,
+) -> Result<()> {
+    require_gt!(amount_in_lamports, 0, InvalidStakeAmount);
+    require_gt!(
+        MAX_STAKE_ACCOUNTS_PER_CENTURION,
+        ctx.accounts.centurion.stake_accounts.len(),
+        MaxStakeAccountsReached
+    );
+
+    // The stake comes out of tracked SOL, without touching the Centurion's rent
+    let centurion_info = ctx.accounts.centurion.to_account_info();
+    let min_rent = Rent::get()?.minimum_balance(centurion_info.data_len());
+    let max_withdrawable = centurion_info.lamports().saturating_sub(min_rent);
+    require_gte!(max_withdrawable, amount_in_lamports, InsufficientFunds);
+    require_gte!(
+        ctx.accounts.centurion.lamport_balance,
+        amount_in_lamports,
+        InsufficientFunds
+    );
+
+    ctx.accounts.centurion.sub_lamports(amount_in_lamports)?;
+    ctx.accounts
+        .stake_account
+        .add_lamports(amount_in_lamports)?;
+
+    let centurion_key = ctx.accounts.centurion.key();
+    anchor_lang::solana_program::program::invoke(
+        &initialize(
+            ctx.accounts.stake_account.key,
+            &Authorized {
+                staker: centurion_key,
+                withdrawer: centurion_key,
+            },
+            &Lockup::default(),
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.rent.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+    )?;
+
+    let centurion = &mut ctx.accounts.centurion;
+    centurion.lamport_balance = centurion
+        .lamport_balance
+        .checked_sub(amount_in_lamports)
+        .ok_or(ArithmeticOverflow)?;
+    centurion
+        .stake_accounts
+        .push(ctx.accounts.stake_account.key());
+    centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    emit!(StakeAccountCreated {
+        centurion: centurion_key,
+        stake_account: ctx.accounts.stake_account.key(),
+        amount: amount_in_lamports,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,86 @@
+use crate::constants::STAKE_CONFIG_ID;
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
+    StakeAccountNotTracked,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::stake_history;
+use solana_stake_interface::{instruction::delegate_stake, program::ID as STAKE_PROGRAM_ID};
+
+// Delegate one of the Centurion's stake accounts to a validator vote account
+
+#[derive(Accounts)]
+pub struct DelegateStake<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+        constraint = centurion.stake_accounts.contains(&stake_account.key()) @StakeAccountNotTracked,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // STAKE ACCOUNTS
+    #[account(mut)]
+    /// CHECK: Tracked by the Centurion and validated by the stake program
+    pub stake_account: UncheckedAccount<'info>,
+    /// CHECK: Validated by the stake program
+    pub vote_account: UncheckedAccount<'info>,
+
+    // SYSVARS
+    pub clock: Sysvar<'info, Clock>,
+    #[account(address = stake_history::ID)]
+    /// CHECK: Stake history sysvar
+    pub stake_history: UncheckedAccount<'info>,
+    #[account(address = STAKE_CONFIG_ID)]
+    /// CHECK: Stake config account, unused by the stake program but still expected
+    pub stake_config: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    #[account(address = STAKE_PROGRAM_ID)]
+    /// CHECK: Stake program
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+pub fn process_delegate_stake(ctx: Context<DelegateStake>) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    anchor_lang::solana_program::program::invoke_signed(
+        &delegate_stake(
+            ctx.accounts.stake_account.key,
+            &ctx.accounts.centurion.key(),
+            ctx.accounts.vote_account.key,
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.vote_account.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_config.to_account_info(),
+            ctx.accounts.centurion.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -0,0 +1,9 @@
+pub mod create_stake_account;
+
+pub mod delegate_stake;
+
+pub mod deactivate_stake;
+
+pub mod withdraw_stake;
+
+pub mod withdraw_stake_to_backup;
@@ -0,0 +1,106 @@
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority,
+    InvalidPasswordSignature, InvalidStakeAmount, StakeAccountNotTracked,
+};
+use crate::events::StakeWithdrawn;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::sysvar::stake_history;
+use solana_stake_interface::{instruction::withdraw, program::ID as STAKE_PROGRAM_ID};
+
+// Withdraw inactive lamports from one of the Centurion's stake accounts back into its lamport_balance.
+// Withdrawing everything closes the stake account and stops tracking it.
+
+#[derive(Accounts)]
+pub struct WithdrawStake<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+        constraint = centurion.stake_accounts.contains(&stake_account.key()) @StakeAccountNotTracked,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // STAKE ACCOUNT
+    #[account(mut)]
+    /// CHECK: Tracked by the Centurion and validated by the stake program
+    pub stake_account: UncheckedAccount<'info>,
+
+    // SYSVARS
+    pub clock: Sysvar<'info, Clock>,
+    #[account(address = stake_history::ID)]
+    /// CHECK: Stake history sysvar
+    pub stake_history: UncheckedAccount<'info>,
+
+    // PROGRAMS
+    #[account(address = STAKE_PROGRAM_ID)]
+    /// CHECK: Stake program
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+pub fn process_withdraw_stake(ctx: Context<WithdrawStake>, amount_in_lamports: u64) -> Result<()> {
+    require_gt!(amount_in_lamports, 0, InvalidStakeAmount);
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    // The Centurion signs as withdrawer and receives the lamports
+    let centurion_key = ctx.accounts.centurion.key();
+    anchor_lang::solana_program::program::invoke_signed(
+        &withdraw(
+            ctx.accounts.stake_account.key,
+            &centurion_key,
+            &centurion_key,
+            amount_in_lamports,
+            None,
+        ),
+        &[
+            ctx.accounts.stake_account.to_account_info(),
+            ctx.accounts.centurion.to_account_info(),
+            ctx.accounts.clock.to_account_info(),
+            ctx.accounts.stake_history.to_account_info(),
+            ctx.accounts.stake_program.to_account_info(),
+        ],
+        signer_seeds,
+    )?;
+
+    let stake_account_key = ctx.accounts.stake_account.key();
+    let closed = ctx.accounts.stake_account.lamports() == 0;
+
+    let centurion = &mut ctx.accounts.centurion;
+    centurion.lamport_balance = centurion
+        .lamport_balance
+        .checked_add(amount_in_lamports)
+        .ok_or(ArithmeticOverflow)?;
+    if closed {
+        centurion
+            .stake_accounts
+            .retain(|stake_account| stake_account != &stake_account_key);
+    }
+    centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    emit!(StakeWithdrawn {
+        centurion: centurion_key,
+        stake_account: stake_account_key,
+        amount: amount_in_lamports,
+        closed,
+    });
+
+    Ok(())
+}
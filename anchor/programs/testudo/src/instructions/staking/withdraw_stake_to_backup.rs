@@ -0,0 +1,102 @@
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidBackupAccount,
+    InvalidPasswordSignature, NoBackupAccountStored, StakeAccountNotTracked,
+};
+use crate::events::StakeMovedToBackup;
+use anchor_lang::prelude::*;
+use solana_stake_interface::{
+    instruction::authorize, program::ID as STAKE_PROGRAM_ID, state::StakeAuthorize,
+};
+
+// Hand one of the Centurion's stake accounts to the backup account by making it staker and withdrawer.
+// The stake keeps earning, whatever its state, and the backup owner manages it from then on.
+
+#[derive(Accounts)]
+pub struct WithdrawStakeToBackup<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+        constraint = centurion.stake_accounts.contains(&stake_account.key()) @StakeAccountNotTracked,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // BACKUP ACCOUNT
+    #[account(
+        // Ensure backup account provided matches account saved in Centurion
+        constraint = backup_account.key() == centurion.backup_owner.ok_or(NoBackupAccountStored)? @InvalidBackupAccount
+    )]
+    /// CHECK: Explicit wrapper for AccountInfo type to emphasize that no checks are performed
+    pub backup_account: UncheckedAccount<'info>,
+
+    // STAKE ACCOUNT
+    #[account(mut)]
+    /// CHECK: Tracked by the Centurion and validated by the stake program
+    pub stake_account: UncheckedAccount<'info>,
+
+    // SYSVARS
+    pub clock: Sysvar<'info, Clock>,
+
+    // PROGRAMS
+    #[account(address = STAKE_PROGRAM_ID)]
+    /// CHECK: Stake program
+    pub stake_program: UncheckedAccount<'info>,
+}
+
+pub fn process_withdraw_stake_to_backup(ctx: Context<WithdrawStakeToBackup>) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    // Move the staker first, while the Centurion is still withdrawer and may sign for both
+    let centurion_key = ctx.accounts.centurion.key();
+    for stake_authorize in [StakeAuthorize::Staker, StakeAuthorize::Withdrawer] {
+        anchor_lang::solana_program::program::invoke_signed(
+            &authorize(
+                ctx.accounts.stake_account.key,
+                &centurion_key,
+                ctx.accounts.backup_account.key,
+                stake_authorize,
+                None,
+            ),
+            &[
+                ctx.accounts.stake_account.to_account_info(),
+                ctx.accounts.clock.to_account_info(),
+                ctx.accounts.centurion.to_account_info(),
+                ctx.accounts.stake_program.to_account_info(),
+            ],
+            signer_seeds,
+        )?;
+    }
+
+    let stake_account_key = ctx.accounts.stake_account.key();
+    let centurion = &mut ctx.accounts.centurion;
+    centurion
+        .stake_accounts
+        .retain(|stake_account| stake_account != &stake_account_key);
+    centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    emit!(StakeMovedToBackup {
+        centurion: centurion_key,
+        stake_account: stake_account_key,
+        backup_owner: ctx.accounts.backup_account.key(),
+    });
+
+    Ok(())
+}
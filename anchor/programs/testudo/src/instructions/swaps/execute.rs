@@ -0,0 +1,209 @@
+use crate::custom_accounts::{
+    centurion::Centurion,
+    legate::{FeeOperation, Legate},
+};
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, MaxAmountInExceeded, SlippageExceeded, UnapprovedSwapRouter,
+};
+use crate::fees::calculate_fee;
+use crate::instructions::swaps::guards::{
+    snapshot_centurion_token_balances, validate_cpi_instruction, verify_no_centurion_token_losses,
+};
+use crate::instructions::swaps::native_sol::{
+    unwrap_centurion_sol, uses_native_sol, wrap_centurion_sol,
+};
+use crate::instructions::swaps::router::{build_router_instruction, RouterInstructionWithIdxs};
+use crate::transfers::{require_approved_transfer_hook, transfer_tokens};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Swap execution shared by every instruction that trades on behalf of a Centurion (user swaps, DCA cycles, ...).
+// Callers validate who may trade; this runs the router with the Centurion signer and enforces the bounds.
+
+pub struct SwapExecution<'a, 'info> {
+    pub centurion: &'a mut Account<'info, Centurion>,
+    pub legate: &'a Account<'info, Legate>,
+    pub source_testudo: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub destination_testudo: &'a mut InterfaceAccount<'info, TokenAccount>,
+    pub source_mint: &'a InterfaceAccount<'info, Mint>,
+    pub destination_mint: &'a InterfaceAccount<'info, Mint>,
+    pub treasury_destination_ata: &'a InterfaceAccount<'info, TokenAccount>,
+    pub source_token_program: &'a Interface<'info, TokenInterface>,
+    pub destination_token_program: &'a Interface<'info, TokenInterface>,
+    pub swap_router: Pubkey,
+    pub rent_recipient: AccountInfo<'info>, // Paid back the rent of temporary wSOL accounts
+    pub min_amount_out: u64,                // Minimum destination increase, net of the protocol fee
+    pub max_amount_in: u64,                 // Maximum source decrease
+}
+
+pub struct SwapOutcome {
+    pub amount_in: u64,  // Measured decrease of the source testudo
+    pub amount_out: u64, // Measured increase of the destination testudo, before the protocol fee
+    pub fee: u64,        // Protocol fee sent to the treasury, in the destination mint
+}
+
+pub fn execute_swap<'info>(
+    swap: SwapExecution<'_, 'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    router_swap: RouterInstructionWithIdxs,
+    router_setup: Vec<RouterInstructionWithIdxs>,
+    router_cleanup: Option<RouterInstructionWithIdxs>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<SwapOutcome> {
+    // Every other Centurion-owned token account handed to the router must come out of the swap no poorer
+    let centurion_key = swap.centurion.key();
+    let guarded_balances_before = snapshot_centurion_token_balances(
+        remaining_accounts,
+        &centurion_key,
+        &[swap.source_testudo.key()],
+    );
+
+    // Selling tracked SOL: wrap up to max_amount_in of it for the router
+    let native_source = uses_native_sol(swap.centurion, &swap.source_mint.key());
+    let native_destination = uses_native_sol(swap.centurion, &swap.destination_mint.key());
+    if native_source {
+        wrap_centurion_sol(
+            swap.centurion,
+            swap.source_testudo,
+            swap.source_token_program,
+            swap.max_amount_in,
+        )?;
+        swap.source_testudo.reload()?;
+    }
+
+    // SETUP INSTRUCTIONS
+    for setup_instruction in router_setup {
+        let (instruction, accounts) =
+            build_router_instruction(&setup_instruction, remaining_accounts, &centurion_key)?;
+        validate_cpi_instruction(swap.legate, &instruction, &centurion_key)?;
+        anchor_lang::solana_program::program::invoke_signed(
+            &instruction,
+            accounts.as_slice(),
+            signer_seeds,
+        )?;
+    }
+
+    // Capture balances before swap
+    let source_balance_before = swap.source_testudo.amount;
+    let dest_balance_before = swap.destination_testudo.amount;
+
+    msg!(
+        "Pre-swap balances - Source: {} ({}), Destination: {} ({})",
+        source_balance_before,
+        swap.source_mint.key(),
+        dest_balance_before,
+        swap.destination_mint.key()
+    );
+
+    // SWAP INSTRUCTION. Must be executed by the router passed in the accounts
+    require_keys_eq!(
+        router_swap.program_id,
+        swap.swap_router,
+        UnapprovedSwapRouter
+    );
+    let (swap_instruction, swap_accounts) =
+        build_router_instruction(&router_swap, remaining_accounts, &centurion_key)?;
+    validate_cpi_instruction(swap.legate, &swap_instruction, &centurion_key)?;
+    anchor_lang::solana_program::program::invoke_signed(
+        &swap_instruction,
+        swap_accounts.as_slice(),
+        signer_seeds,
+    )?;
+
+    // CLEANUP INSTRUCTION
+    if let Some(cleanup_instruction) = router_cleanup {
+        let (instruction, accounts) =
+            build_router_instruction(&cleanup_instruction, remaining_accounts, &centurion_key)?;
+        validate_cpi_instruction(swap.legate, &instruction, &centurion_key)?;
+        anchor_lang::solana_program::program::invoke_signed(
+            &instruction,
+            accounts.as_slice(),
+            signer_seeds,
+        )?;
+    }
+
+    msg!("Router swap execution completed: {}", swap.swap_router);
+
+    verify_no_centurion_token_losses(remaining_accounts, &centurion_key, &guarded_balances_before)?;
+
+    swap.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    // Reload accounts to get updated balances
+    swap.source_testudo.reload()?;
+    swap.destination_testudo.reload()?;
+
+    // Measure what the swap actually moved
+    let amount_in = source_balance_before.saturating_sub(swap.source_testudo.amount);
+    let amount_out = swap
+        .destination_testudo
+        .amount
+        .checked_sub(dest_balance_before)
+        .ok_or(ArithmeticOverflow)?;
+
+    // Protocol fee is taken in the destination mint from the measured output
+    let swap_fee = calculate_fee(
+        swap.legate,
+        FeeOperation::Swap,
+        &swap.destination_mint.key(),
+        amount_out,
+        swap.destination_mint.decimals,
+    )?;
+
+    // Enforce the caller's bounds against the real balance changes
+    require_gte!(swap.max_amount_in, amount_in, MaxAmountInExceeded);
+    require_gte!(
+        amount_out.saturating_sub(swap_fee),
+        swap.min_amount_out,
+        SlippageExceeded
+    );
+
+    if swap_fee > 0 {
+        require_approved_transfer_hook(swap.legate, swap.destination_mint)?;
+        transfer_tokens(
+            swap.destination_token_program.to_account_info(),
+            swap.destination_testudo.to_account_info(),
+            swap.treasury_destination_ata.to_account_info(),
+            swap.centurion.to_account_info(),
+            swap.destination_mint,
+            swap_fee,
+            signer_seeds,
+            remaining_accounts,
+        )?;
+        swap.destination_testudo.reload()?;
+    }
+
+    msg!(
+        "Post-swap balances - Source: {} ({}), Destination: {} ({}), Fee: {}",
+        swap.source_testudo.amount,
+        swap.source_mint.key(),
+        swap.destination_testudo.amount,
+        swap.destination_mint.key(),
+        swap_fee
+    );
+
+    // Unwrap any tracked SOL side back into the Centurion's tracked balance. Unspent input is refunded
+    if native_source {
+        unwrap_centurion_sol(
+            swap.centurion,
+            swap.source_testudo,
+            &swap.rent_recipient,
+            swap.source_token_program,
+            signer_seeds,
+        )?;
+    }
+    if native_destination {
+        unwrap_centurion_sol(
+            swap.centurion,
+            swap.destination_testudo,
+            &swap.rent_recipient,
+            swap.destination_token_program,
+            signer_seeds,
+        )?;
+    }
+
+    Ok(SwapOutcome {
+        amount_in,
+        amount_out,
+        fee: swap_fee,
+    })
+}
@@ -0,0 +1,95 @@
+use crate::constants::{ALLOWED_TOKEN_INSTRUCTIONS, TOKEN_INSTRUCTION_CLOSE_ACCOUNT};
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    CenturionTokenAccountDrained, UnapprovedCpiProgram, UnapprovedTokenInstruction,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+use anchor_spl::token_interface::TokenAccount;
+
+// Balance guards shared by every path that lets an external program act with the Centurion signer.
+
+// Ensure an instruction about to be invoked with the Centurion signer targets a Legate-approved program.
+// Token program calls are further limited to wrapping and unwrapping SOL, and unwrapping must pay the Centurion.
+pub fn validate_cpi_instruction(
+    legate: &Legate,
+    instruction: &Instruction,
+    centurion: &Pubkey,
+) -> Result<()> {
+    // Approved swap routers are implicitly approved CPI targets
+    require_eq!(
+        legate
+            .approved_cpi_programs
+            .contains(&instruction.program_id)
+            || legate
+                .approved_swap_routers
+                .contains(&instruction.program_id),
+        true,
+        UnapprovedCpiProgram
+    );
+
+    if instruction.program_id == anchor_spl::token::ID
+        || instruction.program_id == anchor_spl::token_2022::ID
+    {
+        let discriminator = *instruction.data.first().ok_or(UnapprovedTokenInstruction)?;
+        require_eq!(
+            ALLOWED_TOKEN_INSTRUCTIONS.contains(&discriminator),
+            true,
+            UnapprovedTokenInstruction
+        );
+        // CloseAccount accounts: [account, destination, owner]
+        if discriminator == TOKEN_INSTRUCTION_CLOSE_ACCOUNT {
+            let destination = instruction
+                .accounts
+                .get(1)
+                .ok_or(UnapprovedTokenInstruction)?;
+            require_keys_eq!(destination.pubkey, *centurion, UnapprovedTokenInstruction);
+        }
+    }
+    Ok(())
+}
+
+// Token balance of every Centurion-owned token account in `accounts`, except those in `excluded`.
+// Accounts that are not token accounts, or belong to someone else, are ignored.
+pub fn snapshot_centurion_token_balances(
+    accounts: &[AccountInfo],
+    centurion: &Pubkey,
+    excluded: &[Pubkey],
+) -> Vec<(Pubkey, u64)> {
+    let mut balances: Vec<(Pubkey, u64)> = Vec::new();
+    for account in accounts {
+        if excluded.contains(account.key) || balances.iter().any(|(key, _)| key == account.key) {
+            continue;
+        }
+        if let Some(amount) = centurion_token_balance(account, centurion) {
+            balances.push((*account.key, amount));
+        }
+    }
+    balances
+}
+
+// Ensure none of the snapshotted token accounts lost funds. A closed account counts as empty
+pub fn verify_no_centurion_token_losses(
+    accounts: &[AccountInfo],
+    centurion: &Pubkey,
+    balances_before: &[(Pubkey, u64)],
+) -> Result<()> {
+    for (key, amount_before) in balances_before {
+        let amount_after = accounts
+            .iter()
+            .find(|account| account.key == key)
+            .and_then(|account| centurion_token_balance(account, centurion))
+            .unwrap_or(0);
+        require_gte!(amount_after, *amount_before, CenturionTokenAccountDrained);
+    }
+    Ok(())
+}
+
+fn centurion_token_balance(account: &AccountInfo, centurion: &Pubkey) -> Option<u64> {
+    if *account.owner != anchor_spl::token::ID && *account.owner != anchor_spl::token_2022::ID {
+        return None;
+    }
+    let data = account.try_borrow_data().ok()?;
+    let token_account = TokenAccount::try_deserialize(&mut &data[..]).ok()?;
+    (token_account.owner == *centurion).then_some(token_account.amount)
+}
@@ -1 +1,9 @@
 pub mod swap;
+
+pub mod guards;
+
+pub mod router;
+
+pub mod native_sol;
+
+pub mod execute;
@@ -0,0 +1,125 @@
+use crate::constants::{SOL_DECIMALS, WSOL_MINT};
+use crate::custom_accounts::centurion::Centurion;
+use crate::custom_accounts::legate::{FeeOperation, Legate};
+use crate::errors::ErrorCode::{ArithmeticOverflow, InsufficientFunds};
+use crate::fees::calculate_fee;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, sync_native, CloseAccount, SyncNative, TokenAccount, TokenInterface,
+};
+
+// Native SOL side of a swap. SOL tracked in `lamport_balance` is wrapped into a temporary wSOL account
+// owned by the Centurion for the router, and anything left in it afterwards is unwrapped back. A Centurion
+// with a wSOL testudo trades that testudo directly instead, like any other mint.
+
+// Extra seed of the temporary wSOL account, so it never collides with a wSOL testudo ([centurion, mint])
+pub const NATIVE_SOL_SEED: &[u8] = b"wsol";
+
+// Whether the `mint` side of a swap is the Centurion's tracked SOL, wrapped in the temporary wSOL account
+pub fn uses_native_sol(centurion: &Centurion, mint: &Pubkey) -> bool {
+    *mint == WSOL_MINT
+        && !centurion
+            .testudos
+            .iter()
+            .any(|testudo| testudo.token_mint == WSOL_MINT)
+}
+
+// Extra seed of the token account on the `mint` side of a swap. Empty unless it is the temporary wSOL
+// account, which leaves the usual testudo address unchanged.
+pub fn native_sol_seed(centurion: &Centurion, mint: &Pubkey) -> &'static [u8] {
+    if uses_native_sol(centurion, mint) {
+        NATIVE_SOL_SEED
+    } else {
+        b""
+    }
+}
+
+// Move `amount` of the Centurion's tracked SOL into the temporary wSOL account and debit `lamport_balance`
+pub fn wrap_centurion_sol<'info>(
+    centurion: &mut Account<'info, Centurion>,
+    wsol_account: &InterfaceAccount<'info, TokenAccount>,
+    token_program: &Interface<'info, TokenInterface>,
+    amount: u64,
+) -> Result<()> {
+    // Ensure the wrap won't take the Centurion below rent exemption
+    let min_rent = Rent::get()?.minimum_balance(centurion.to_account_info().data_len());
+    let max_wrappable = centurion.get_lamports().saturating_sub(min_rent);
+    require_gte!(max_wrappable, amount, InsufficientFunds);
+    require_gte!(centurion.lamport_balance, amount, InsufficientFunds);
+
+    centurion.sub_lamports(amount)?;
+    wsol_account.add_lamports(amount)?;
+    centurion.lamport_balance = centurion
+        .lamport_balance
+        .checked_sub(amount)
+        .ok_or(ArithmeticOverflow)?;
+
+    sync_native(CpiContext::new(
+        token_program.to_account_info(),
+        SyncNative {
+            account: wsol_account.to_account_info(),
+        },
+    ))
+}
+
+// Close the temporary wSOL account into the Centurion and credit its wSOL balance to `lamport_balance`.
+// The account's rent goes back to `rent_recipient`, who paid for it. Returns the amount unwrapped.
+pub fn unwrap_centurion_sol<'info>(
+    centurion: &mut Account<'info, Centurion>,
+    wsol_account: &InterfaceAccount<'info, TokenAccount>,
+    rent_recipient: &AccountInfo<'info>,
+    token_program: &Interface<'info, TokenInterface>,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let unwrapped = wsol_account.amount;
+    let rent_lamports = wsol_account
+        .get_lamports()
+        .checked_sub(unwrapped)
+        .ok_or(ArithmeticOverflow)?;
+
+    close_account(CpiContext::new_with_signer(
+        token_program.to_account_info(),
+        CloseAccount {
+            account: wsol_account.to_account_info(),
+            destination: centurion.to_account_info(),
+            authority: centurion.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    centurion.sub_lamports(rent_lamports)?;
+    rent_recipient.add_lamports(rent_lamports)?;
+    centurion.lamport_balance = centurion
+        .lamport_balance
+        .checked_add(unwrapped)
+        .ok_or(ArithmeticOverflow)?;
+
+    Ok(unwrapped)
+}
+
+// Charge the internal transfer fee on `amount` lamports moved between `lamport_balance` and the wSOL testudo.
+// The fee is paid to the treasury out of the tracked SOL. Returns the fee
+pub fn charge_internal_transfer_fee<'info>(
+    centurion: &mut Account<'info, Centurion>,
+    treasury: &AccountInfo<'info>,
+    legate: &Legate,
+    amount: u64,
+) -> Result<u64> {
+    let fee = calculate_fee(
+        legate,
+        FeeOperation::InternalTransfer,
+        &WSOL_MINT,
+        amount,
+        SOL_DECIMALS,
+    )?;
+    if fee > 0 {
+        require_gte!(centurion.lamport_balance, fee, InsufficientFunds);
+        centurion.sub_lamports(fee)?;
+        treasury.add_lamports(fee)?;
+        centurion.lamport_balance = centurion
+            .lamport_balance
+            .checked_sub(fee)
+            .ok_or(ArithmeticOverflow)?;
+    }
+    Ok(fee)
+}
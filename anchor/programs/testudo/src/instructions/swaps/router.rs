@@ -0,0 +1,43 @@
+use crate::errors::ErrorCode::InvalidRemainingAccounts;
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::instruction::Instruction;
+
+// Router adapter. A swap is executed through any Legate-approved router program (Jupiter, an AMM, ...)
+// by replaying router-built instructions whose accounts are passed as remaining accounts.
+
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub struct RouterInstructionWithIdxs {
+    pub program_id: Pubkey,
+    pub accounts_idxs: Vec<u8>, // Index of each instruction account within remaining_accounts
+    pub data: Vec<u8>,
+}
+
+// Resolve a router instruction against the remaining accounts. The Centurion is marked as a signer so
+// the router can move its tokens once the instruction is invoked with the Centurion seeds.
+pub fn build_router_instruction<'info>(
+    router_instruction: &RouterInstructionWithIdxs,
+    remaining_accounts: &[AccountInfo<'info>],
+    centurion: &Pubkey,
+) -> Result<(Instruction, Vec<AccountInfo<'info>>)> {
+    let accounts = router_instruction
+        .accounts_idxs
+        .iter()
+        .map(|idx| {
+            remaining_accounts
+                .get(*idx as usize)
+                .cloned()
+                .ok_or(InvalidRemainingAccounts.into())
+        })
+        .collect::<Result<Vec<AccountInfo<'info>>>>()?;
+
+    let instruction = Instruction::new_with_bytes(
+        router_instruction.program_id,
+        &router_instruction.data,
+        accounts
+            .iter()
+            .flat_map(|acc| acc.to_account_metas(Some(acc.is_signer || acc.key == centurion)))
+            .collect(),
+    );
+
+    Ok((instruction, accounts))
+}
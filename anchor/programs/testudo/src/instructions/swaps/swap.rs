@@ -1,26 +1,19 @@
-use crate::custom_accounts::{
-    centurion::{Centurion, TestudoData},
-    legate::Legate,
-};
+use crate::custom_accounts::{centurion::Centurion, legate::Legate};
 use crate::errors::ErrorCode::{
-    CenturionNotInitialized, InvalidAuthority, InvalidPasswordSignature, InvalidRemainingAccounts,
-    InvalidTokenMint, InvalidTreasuryAccount, LegateNotInitialized,
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
+    InvalidTokenMint, InvalidTreasuryAccount, LegateNotInitialized, LegateNotMigrated,
+    UnapprovedSwapRouter,
 };
+use crate::events::SwapExecuted;
+use crate::instructions::swaps::execute::{execute_swap, SwapExecution};
+use crate::instructions::swaps::native_sol::{native_sol_seed, uses_native_sol};
+use crate::instructions::swaps::router::RouterInstructionWithIdxs;
 use anchor_lang::prelude::*;
-use anchor_lang::solana_program::instruction::Instruction;
 use anchor_spl::{
     associated_token::AssociatedToken,
-    token::{Mint, TokenAccount},
-    token_interface::TokenInterface,
+    token_interface::{Mint, TokenAccount, TokenInterface},
 };
 
-#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
-pub struct JupiterInstructionWithIdxs {
-    pub program_id: Pubkey,
-    pub accounts_idxs: Vec<u8>,
-    pub data: Vec<u8>,
-}
-
 #[derive(Accounts)]
 pub struct Swap<'info> {
     // SIGNERS
@@ -36,51 +29,69 @@ pub struct Swap<'info> {
         seeds = [b"centurion".as_ref(), authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     // CENTURION
     pub centurion: Account<'info, Centurion>,
 
-    // SOURCE TOKEN ACCOUNT
+    // SOURCE TOKEN ACCOUNT. For SOL without a wSOL testudo, a temporary wSOL account closed again at the end of the swap
     #[account(
         init_if_needed,
         payer = authority,
         token::mint = source_mint,
         token::authority = centurion,
-        token::token_program = token_program,
-        seeds = [centurion.key().as_ref(), source_mint.key().as_ref()],
+        token::token_program = source_token_program,
+        seeds = [centurion.key().as_ref(), source_mint.key().as_ref(), native_sol_seed(&centurion, &source_mint.key())],
         bump
     )]
-    pub source_testudo: Account<'info, TokenAccount>,
+    pub source_testudo: InterfaceAccount<'info, TokenAccount>,
 
-    // DESTINATION TOKEN ACCOUNT
+    // DESTINATION TOKEN ACCOUNT. For SOL without a wSOL testudo, a temporary wSOL account closed again at the end of the swap
     #[account(
         init_if_needed,
         payer = authority,
         token::mint = destination_mint,
         token::authority = centurion,
-        token::token_program = token_program,
-        seeds = [centurion.key().as_ref(), destination_mint.key().as_ref()],
+        token::token_program = destination_token_program,
+        seeds = [centurion.key().as_ref(), destination_mint.key().as_ref(), native_sol_seed(&centurion, &destination_mint.key())],
         bump
     )]
-    pub destination_testudo: Account<'info, TokenAccount>,
+    pub destination_testudo: InterfaceAccount<'info, TokenAccount>,
 
-    // MINTS
-    pub source_mint: Account<'info, Mint>,
+    // MINTS. Each mint must be owned by the token program passed for its side of the swap
+    #[account(
+        mint::token_program = source_token_program,
+    )]
+    pub source_mint: InterfaceAccount<'info, Mint>,
     #[account(
+        mint::token_program = destination_token_program,
         constraint = legate.testudo_token_whitelist.iter().any(|t| t.token_mint == destination_mint.key()) @InvalidTokenMint
     )]
-    pub destination_mint: Account<'info, Mint>,
+    pub destination_mint: InterfaceAccount<'info, Mint>,
 
     // TREASURY
+    #[account(
+        constraint = legate.treasury_acc == treasury.key() @InvalidTreasuryAccount
+    )]
     /// CHECK: Explicit wrapper for AccountInfo type to emphasize that no checks are performed
     pub treasury: UncheckedAccount<'info>,
 
+    // TREASURY ATA (DESTINATION MINT). Receives the protocol swap fee
+    #[account(
+        mut,
+        associated_token::mint = destination_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = destination_token_program,
+    )]
+    pub treasury_destination_ata: InterfaceAccount<'info, TokenAccount>,
+
     // LEGATE
     #[account(
         seeds = [b"legate"],
         bump = legate.bump,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 
@@ -88,23 +99,28 @@ pub struct Swap<'info> {
     pub system_program: Program<'info, System>,
     pub associated_token_program: Program<'info, AssociatedToken>,
     #[account(
-        constraint = token_program.key() == anchor_spl::token::ID || token_program.key() == anchor_spl::token_2022::ID
+        constraint = source_token_program.key() == anchor_spl::token::ID || source_token_program.key() == anchor_spl::token_2022::ID
     )]
-    pub token_program: Interface<'info, TokenInterface>,
+    pub source_token_program: Interface<'info, TokenInterface>,
     #[account(
-        constraint = legate.treasury_acc == treasury.key() @InvalidTreasuryAccount
+        constraint = destination_token_program.key() == anchor_spl::token::ID || destination_token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub destination_token_program: Interface<'info, TokenInterface>,
+    /// CHECK: Any router program approved by the Legate (Jupiter, an AMM, ...)
+    #[account(
+        executable,
+        constraint = legate.approved_swap_routers.contains(&swap_router.key()) @UnapprovedSwapRouter
     )]
-    /// CHECK: Jupiter program
-    #[account(address = pubkey!("JUP6LkbZbjS1jKKwapdHNy74zcZ3tLUZoi5QNyVTaV4"))]
-    pub jupiter_program: UncheckedAccount<'info>,
+    pub swap_router: UncheckedAccount<'info>,
 }
 
-pub fn process_swap(
-    ctx: Context<Swap>,
-    jupiter_swap: JupiterInstructionWithIdxs,
-    jupiter_setup: Vec<JupiterInstructionWithIdxs>, // This involves SOL -> WSOL version and other accounts setup
-    jupiter_cleanup: JupiterInstructionWithIdxs,    // Idxs to clean up accounts etc post swap
-    testudo_data: Vec<TestudoData>,
+pub fn process_swap<'info>(
+    ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+    router_swap: RouterInstructionWithIdxs,
+    router_setup: Vec<RouterInstructionWithIdxs>, // This involves SOL -> WSOL version and other accounts setup
+    router_cleanup: Option<RouterInstructionWithIdxs>, // Idxs to clean up accounts etc post swap
+    min_amount_out: u64, // Minimum destination increase, net of the protocol fee
+    max_amount_in: u64,  // Maximum source decrease
 ) -> Result<()> {
     msg!(
         "Starting swap process for authority: {}",
@@ -118,142 +134,58 @@ pub fn process_swap(
     );
     msg!("Password signature validation successful");
 
+    // Register a newly created destination testudo. The temporary wSOL account is never registered
+    let destination_mint = ctx.accounts.destination_mint.key();
     let centurion = &mut ctx.accounts.centurion;
-    for data in testudo_data {
-        if !centurion
+    if !uses_native_sol(centurion, &destination_mint)
+        && !centurion
             .testudos
             .iter()
-            .any(|t| t.token_mint == data.token_mint)
-            && data.testudo_pubkey != pubkey!("So11111111111111111111111111111111111111112")
-        {
-            centurion.testudos.push(data.clone());
-        }
+            .any(|t| t.token_mint == destination_mint)
+    {
+        centurion.register_testudo(
+            destination_mint,
+            ctx.accounts.destination_testudo.key(),
+            ctx.accounts.legate.max_testudos_per_user,
+        )?;
     }
 
-    let remaining_accounts = ctx.remaining_accounts.to_vec();
     let signer_seeds: &[&[&[u8]]] = &[&[
         b"centurion",
         ctx.accounts.authority.key.as_ref(),
         &[ctx.bumps.centurion],
     ]];
 
-    // SETUP INSTRUCTIONS
-    for setup_instruction in jupiter_setup {
-        let setup_instruction_accounts: Vec<AccountInfo> = setup_instruction
-            .accounts_idxs
-            .iter()
-            .map(|idx| {
-                remaining_accounts
-                    .get(*idx as usize)
-                    .ok_or(InvalidRemainingAccounts)
-                    .unwrap()
-                    .clone()
-            })
-            .collect();
-
-        let jupiter_setup_instruction = Instruction::new_with_bytes(
-            setup_instruction.program_id,
-            &setup_instruction.data,
-            setup_instruction_accounts
-                .clone()
-                .iter_mut()
-                .flat_map(|acc| acc.to_account_metas(Some(acc.is_signer)))
-                .collect(),
-        );
-
-        anchor_lang::solana_program::program::invoke_signed(
-            &jupiter_setup_instruction,
-            setup_instruction_accounts.as_slice(),
-            signer_seeds,
-        )?;
-    }
-
-    // Capture balances before swap
-    let source_balance_before = ctx.accounts.source_testudo.amount;
-    let dest_balance_before = ctx.accounts.destination_testudo.amount;
-
-    msg!(
-        "Pre-swap balances - Source: {} ({}), Destination: {} ({})",
-        source_balance_before,
-        ctx.accounts.source_mint.key(),
-        dest_balance_before,
-        ctx.accounts.destination_mint.key()
-    );
-
-    // SWAP INSTRUCTIONS
-    let swap_accounts: Vec<AccountInfo> = jupiter_swap
-        .accounts_idxs
-        .iter()
-        .map(|idx| {
-            remaining_accounts
-                .get(*idx as usize)
-                .ok_or(InvalidRemainingAccounts)
-                .unwrap()
-                .clone()
-        })
-        .collect();
-
-    // Build the Jupiter instruction - jupiter_data param contains all data required for that swap
-    // remaining_accounts contains all the accounts required for the swap
-    let swap_instruction = Instruction::new_with_bytes(
-        ctx.accounts.jupiter_program.key(),
-        &jupiter_swap.data,
-        swap_accounts
-            .clone()
-            .iter_mut()
-            .flat_map(|acc| acc.to_account_metas(Some(acc.is_signer)))
-            .collect(),
-    );
-
-    anchor_lang::solana_program::program::invoke_signed(
-        &swap_instruction,
-        swap_accounts.as_slice(),
-        signer_seeds,
-    )?;
-
-    // CLEANUP INSTRUCTIONS
-    let cleanup_accounts: Vec<AccountInfo> = jupiter_cleanup
-        .accounts_idxs
-        .iter()
-        .map(|idx| {
-            remaining_accounts
-                .get(*idx as usize)
-                .ok_or(InvalidRemainingAccounts)
-                .unwrap()
-                .clone()
-        })
-        .collect();
-
-    let cleanup_instruction = Instruction::new_with_bytes(
-        jupiter_cleanup.program_id,
-        &jupiter_cleanup.data,
-        cleanup_accounts
-            .clone()
-            .iter_mut()
-            .flat_map(|acc| acc.to_account_metas(Some(acc.is_signer)))
-            .collect(),
-    );
-
-    anchor_lang::solana_program::program::invoke_signed(
-        &cleanup_instruction,
-        cleanup_accounts.as_slice(),
+    let outcome = execute_swap(
+        SwapExecution {
+            centurion: &mut ctx.accounts.centurion,
+            legate: &ctx.accounts.legate,
+            source_testudo: &mut ctx.accounts.source_testudo,
+            destination_testudo: &mut ctx.accounts.destination_testudo,
+            source_mint: &ctx.accounts.source_mint,
+            destination_mint: &ctx.accounts.destination_mint,
+            treasury_destination_ata: &ctx.accounts.treasury_destination_ata,
+            source_token_program: &ctx.accounts.source_token_program,
+            destination_token_program: &ctx.accounts.destination_token_program,
+            swap_router: ctx.accounts.swap_router.key(),
+            rent_recipient: ctx.accounts.authority.to_account_info(),
+            min_amount_out,
+            max_amount_in,
+        },
+        ctx.remaining_accounts,
+        router_swap,
+        router_setup,
+        router_cleanup,
         signer_seeds,
     )?;
 
-    msg!("Jupiter swap execution completed");
-
-    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
-
-    // Reload accounts to get updated balances
-    ctx.accounts.source_testudo.reload()?;
-    ctx.accounts.destination_testudo.reload()?;
-
-    msg!(
-        "Post-swap balances - Source: {} ({}), Destination: {} ({})",
-        ctx.accounts.source_testudo.amount,
-        ctx.accounts.source_mint.key(),
-        ctx.accounts.destination_testudo.amount,
-        ctx.accounts.destination_mint.key()
-    );
+    emit!(SwapExecuted {
+        centurion: ctx.accounts.centurion.key(),
+        source_mint: ctx.accounts.source_mint.key(),
+        destination_mint: ctx.accounts.destination_mint.key(),
+        amount_in: outcome.amount_in,
+        amount_out: outcome.amount_out,
+        fee: outcome.fee,
+    });
     Ok(())
 }
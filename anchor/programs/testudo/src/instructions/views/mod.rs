@@ -0,0 +1,7 @@
+pub mod quote_fee;
+
+pub mod quote_usd_value;
+
+pub mod quote_sol_balance;
+
+pub mod quote_yield_position;
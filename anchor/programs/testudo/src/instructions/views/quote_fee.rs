@@ -0,0 +1,43 @@
+use crate::constants::{SOL_DECIMALS, WSOL_MINT};
+use crate::custom_accounts::legate::{FeeOperation, Legate};
+use crate::errors::ErrorCode::{LegateNotInitialized, LegateNotMigrated, UnsupportedTokenMint};
+use crate::fees::calculate_fee;
+use anchor_lang::prelude::*;
+
+// Read-only quote of the protocol fee for an operation, so frontends can show it before sending.
+// Called through simulation (`.view()`), the fee is returned as instruction return data.
+
+#[derive(Accounts)]
+pub struct QuoteFee<'info> {
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+}
+
+pub fn process_quote_fee(
+    ctx: Context<QuoteFee>,
+    operation: FeeOperation,
+    mint: Pubkey,
+    amount: u64,
+) -> Result<u64> {
+    let legate = &ctx.accounts.legate;
+
+    // Native SOL is quoted through the wSOL mint, whether or not wSOL is whitelisted
+    let decimals = if mint == WSOL_MINT {
+        SOL_DECIMALS
+    } else {
+        legate
+            .testudo_token_whitelist
+            .iter()
+            .find(|t| t.token_mint == mint)
+            .ok_or(UnsupportedTokenMint)?
+            .token_decimals
+    };
+
+    calculate_fee(legate, operation, &mint, amount, decimals)
+}
@@ -0,0 +1,39 @@
+use crate::constants::WSOL_MINT;
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::TokenAccount;
+
+// Read-only combined SOL balance of a Centurion: native SOL tracked in `lamport_balance` plus its wSOL testudo.
+// Called through simulation (`.view()`), the balance in lamports is returned as instruction return data.
+
+#[derive(Accounts)]
+pub struct QuoteSolBalance<'info> {
+    // CENTURION
+    #[account(
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // WSOL TESTUDO. Omitted when the Centurion has none
+    #[account(
+        seeds = [centurion.key().as_ref(), WSOL_MINT.as_ref()],
+        bump,
+    )]
+    pub wsol_testudo: Option<InterfaceAccount<'info, TokenAccount>>,
+}
+
+pub fn process_quote_sol_balance(ctx: Context<QuoteSolBalance>) -> Result<u64> {
+    let wrapped = ctx
+        .accounts
+        .wsol_testudo
+        .as_ref()
+        .map_or(0, |testudo| testudo.amount);
+
+    ctx.accounts
+        .centurion
+        .lamport_balance
+        .checked_add(wrapped)
+        .ok_or(ArithmeticOverflow.into())
+}
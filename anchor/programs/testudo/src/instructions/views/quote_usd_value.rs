@@ -0,0 +1,52 @@
+use crate::constants::{SOL_DECIMALS, WSOL_MINT};
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{LegateNotInitialized, LegateNotMigrated, UnsupportedTokenMint};
+use crate::oracle::value_in_usd;
+use anchor_lang::prelude::*;
+
+// Read-only USD value of an amount of a whitelisted mint, in micro-USD, at the mint's registered oracle price.
+// Called through simulation (`.view()`), the value is returned as instruction return data.
+
+#[derive(Accounts)]
+pub struct QuoteUsdValue<'info> {
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // PRICE FEED OF THE MINT
+    /// CHECK: Must be the price feed registered on the Legate. Checked and parsed by the oracle module
+    pub price_account: UncheckedAccount<'info>,
+}
+
+pub fn process_quote_usd_value(
+    ctx: Context<QuoteUsdValue>,
+    mint: Pubkey,
+    amount: u64,
+) -> Result<u64> {
+    let legate = &ctx.accounts.legate;
+
+    // Native SOL is valued through the wSOL mint
+    let decimals = if mint == WSOL_MINT {
+        SOL_DECIMALS
+    } else {
+        legate
+            .testudo_token_whitelist
+            .iter()
+            .find(|t| t.token_mint == mint)
+            .ok_or(UnsupportedTokenMint)?
+            .token_decimals
+    };
+
+    value_in_usd(
+        legate,
+        &mint,
+        amount,
+        decimals,
+        &ctx.accounts.price_account.to_account_info(),
+    )
+}
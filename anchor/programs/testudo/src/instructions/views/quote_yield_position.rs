@@ -0,0 +1,51 @@
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, YieldPositionNotFound,
+};
+use crate::yield_adapter::query_position_value;
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+// Read-only value of a Centurion's yield position in the underlying, as reported by the adapter.
+// Called through simulation (`.view()`), the value is returned as instruction return data.
+
+#[derive(Accounts)]
+pub struct QuoteYieldPosition<'info> {
+    // CENTURION
+    #[account(
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        constraint = centurion.yield_positions.iter().any(|position| {
+            position.adapter_program == adapter_program.key()
+                && position.vault == vault.key()
+                && position.position_mint == position_mint.key()
+        }) @YieldPositionNotFound,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // YIELD ADAPTER
+    /// CHECK: Matched against the Centurion's recorded position
+    pub adapter_program: UncheckedAccount<'info>,
+    /// CHECK: Matched against the Centurion's recorded position
+    pub vault: UncheckedAccount<'info>,
+    /// CHECK: Validated by the adapter
+    pub vault_reserve: UncheckedAccount<'info>,
+
+    // POSITION
+    pub position_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        seeds = [centurion.key().as_ref(), position_mint.key().as_ref(), b"yield".as_ref()],
+        bump,
+    )]
+    pub position_account: InterfaceAccount<'info, TokenAccount>,
+}
+
+pub fn process_quote_yield_position(ctx: Context<QuoteYieldPosition>) -> Result<u64> {
+    query_position_value(
+        ctx.accounts.adapter_program.to_account_info(),
+        ctx.accounts.vault.to_account_info(),
+        ctx.accounts.vault_reserve.to_account_info(),
+        ctx.accounts.position_mint.to_account_info(),
+        ctx.accounts.position_account.amount,
+    )
+}
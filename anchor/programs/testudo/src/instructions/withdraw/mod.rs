@@ -5,3 +5,5 @@ pub mod withdraw_spl;
 pub mod withdraw_spl_to_backup;
 
 pub mod withdraw_sol_to_backup;
+
+pub mod rescue_tokens;
@@ -0,0 +1,165 @@
+use crate::custom_accounts::centurion::Centurion;
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority, InvalidPasswordSignature,
+    RegisteredTestudoMint, VaultedNftMint, YieldPositionMint,
+};
+use crate::events::TokensRescued;
+use crate::transfers::{harvest_withheld_fees, transfer_tokens};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+
+// Recover tokens sent to a Centurion-owned token account for a mint without a testudo (airdrops, mistaken
+// sends). The full balance goes to the authority and the account is closed. No whitelist is required and no
+// protocol fee is charged; testudo mints, vaulted NFTs and yield position mints must go through their own
+// withdraw instructions.
+
+#[derive(Accounts)]
+pub struct RescueTokens<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // STRAY TOKEN ACCOUNT. Any token account the Centurion owns
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+    )]
+    pub stray_token_account: InterfaceAccount<'info, TokenAccount>,
+
+    // AUTHORITY ATA
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = mint,
+        associated_token::authority = authority,
+        associated_token::token_program = token_program,
+    )]
+    pub authority_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // MINT. Writable so withheld transfer fees can be harvested into it
+    #[account(
+        mut,
+        mint::token_program = token_program,
+    )]
+    pub mint: InterfaceAccount<'info, Mint>,
+
+    // PROGRAMS
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID || token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    #[account(
+        constraint = associated_token_program.key() == anchor_spl::associated_token::ID,
+    )]
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    #[account(
+        constraint = system_program.key() == anchor_lang::system_program::ID,
+    )]
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_rescue_tokens<'info>(
+    ctx: Context<'_, '_, '_, 'info, RescueTokens<'info>>,
+) -> Result<()> {
+    let centurion = &ctx.accounts.centurion;
+
+    // Testudo balances are only paid out through the withdraw instructions
+    require_eq!(
+        centurion
+            .testudos
+            .iter()
+            .any(|testudo| testudo.token_mint == ctx.accounts.mint.key()),
+        false,
+        RegisteredTestudoMint
+    );
+
+    // Vaulted NFTs are only paid out through the NFT withdraw instructions
+    require_eq!(
+        centurion.nfts.contains(&ctx.accounts.mint.key()),
+        false,
+        VaultedNftMint
+    );
+
+    // Position tokens are only redeemed through the yield position instructions
+    require_eq!(
+        centurion
+            .yield_positions
+            .iter()
+            .any(|position| position.position_mint == ctx.accounts.mint.key()),
+        false,
+        YieldPositionMint
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    // Hooked mints need their extra accounts in remaining_accounts
+    let amount = ctx.accounts.stray_token_account.amount;
+    let mut amount_received = 0;
+    if amount > 0 {
+        amount_received = transfer_tokens(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.stray_token_account.to_account_info(),
+            ctx.accounts.authority_ata.to_account_info(),
+            centurion.to_account_info(),
+            &ctx.accounts.mint,
+            amount,
+            signer_seeds,
+            ctx.remaining_accounts,
+        )?;
+    }
+
+    // Fees withheld from the incoming transfers would block the close
+    harvest_withheld_fees(
+        ctx.accounts.token_program.to_account_info(),
+        &ctx.accounts.mint,
+        ctx.accounts.stray_token_account.to_account_info(),
+    )?;
+
+    // Close the account, returning its rent to the authority
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.stray_token_account.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: centurion.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+
+    emit!(TokensRescued {
+        centurion: centurion.key(),
+        mint: ctx.accounts.mint.key(),
+        token_account: ctx.accounts.stray_token_account.key(),
+        amount,
+        amount_received,
+    });
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
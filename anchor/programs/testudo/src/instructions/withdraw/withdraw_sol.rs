@@ -1,9 +1,12 @@
+use crate::constants::{SOL_DECIMALS, WSOL_MINT};
 use crate::custom_accounts::centurion::Centurion;
-use crate::custom_accounts::legate::Legate;
+use crate::custom_accounts::legate::{FeeOperation, Legate};
 use crate::errors::ErrorCode::{
-    ArithmeticOverflow, CenturionNotInitialized, InsufficientFunds, InvalidAuthority,
-    InvalidPasswordSignature, InvalidTreasuryAccount, LegateNotInitialized,
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds,
+    InvalidAuthority, InvalidPasswordSignature, InvalidTreasuryAccount, LegateNotInitialized,
+    LegateNotMigrated,
 };
+use crate::fees::calculate_fee;
 use anchor_lang::prelude::*;
 
 // Withdraw native SOL from a Centurion account
@@ -24,6 +27,7 @@ pub struct WithdrawSol<'info> {
         seeds = [b"centurion".as_ref(), authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
@@ -33,6 +37,7 @@ pub struct WithdrawSol<'info> {
         seeds = [b"legate"],
         bump = legate.bump,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 
@@ -77,11 +82,13 @@ pub fn process_withdraw_sol(ctx: Context<WithdrawSol>, amount_in_lamports: u64)
         InsufficientFunds
     );
 
-    let withdraw_fee = amount_in_lamports
-        .checked_mul(ctx.accounts.legate.percent_for_fees as u64)
-        .unwrap_or(0)
-        .checked_div(10000)
-        .unwrap_or(0);
+    let withdraw_fee = calculate_fee(
+        &ctx.accounts.legate,
+        FeeOperation::Withdraw,
+        &WSOL_MINT,
+        amount_in_lamports,
+        SOL_DECIMALS,
+    )?;
     let amount_after_fee = amount_in_lamports
         .checked_sub(withdraw_fee)
         .ok_or(ArithmeticOverflow)?;
@@ -1,10 +1,12 @@
+use crate::constants::{SOL_DECIMALS, WSOL_MINT};
 use crate::custom_accounts::centurion::Centurion;
-use crate::custom_accounts::legate::Legate;
+use crate::custom_accounts::legate::{FeeOperation, Legate};
 use crate::errors::ErrorCode::{
-    ArithmeticOverflow, CenturionNotInitialized, InsufficientFunds, InvalidAuthority,
-    InvalidBackupAccount, InvalidPasswordSignature, InvalidTreasuryAccount, LegateNotInitialized,
-    NoBackupAccountStored,
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds,
+    InvalidAuthority, InvalidBackupAccount, InvalidPasswordSignature, InvalidTreasuryAccount,
+    LegateNotInitialized, LegateNotMigrated, NoBackupAccountStored,
 };
+use crate::fees::calculate_fee;
 use anchor_lang::prelude::*;
 
 // Withdraw native SOL from a Centurion account to the backup account
@@ -25,6 +27,7 @@ pub struct WithdrawSolToBackup<'info> {
         seeds = [b"centurion".as_ref(), authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
@@ -43,6 +46,7 @@ pub struct WithdrawSolToBackup<'info> {
         seeds = [b"legate"],
         bump = legate.bump,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 
@@ -98,11 +102,13 @@ pub fn process_withdraw_sol_to_backup(ctx: Context<WithdrawSolToBackup>) -> Resu
     // Only proceed if there's something to withdraw
     require_gt!(withdraw_amount, 0, InsufficientFunds);
 
-    let withdraw_fee = withdraw_amount
-        .checked_mul(ctx.accounts.legate.percent_for_fees as u64)
-        .unwrap_or(0)
-        .checked_div(10000)
-        .unwrap_or(0);
+    let withdraw_fee = calculate_fee(
+        &ctx.accounts.legate,
+        FeeOperation::BackupRecovery,
+        &WSOL_MINT,
+        withdraw_amount,
+        SOL_DECIMALS,
+    )?;
     let amount_after_fee = withdraw_amount
         .checked_sub(withdraw_fee)
         .ok_or(ArithmeticOverflow)?;
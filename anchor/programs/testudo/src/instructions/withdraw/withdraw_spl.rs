@@ -1,13 +1,18 @@
 use crate::custom_accounts::centurion::Centurion;
-use crate::custom_accounts::legate::Legate;
+use crate::custom_accounts::legate::{FeeOperation, Legate};
 use crate::errors::ErrorCode::{
-    ArithmeticOverflow, CenturionNotInitialized, InsufficientFunds, InvalidATA, InvalidAuthority,
-    InvalidPasswordSignature, InvalidTokenMint, InvalidTreasuryAccount, LegateNotInitialized,
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds,
+    InvalidATA, InvalidAuthority, InvalidPasswordSignature, InvalidTokenMint,
+    InvalidTreasuryAccount, LegateNotInitialized, LegateNotMigrated,
+};
+use crate::events::SplWithdrawn;
+use crate::fees::calculate_fee;
+use crate::transfers::{
+    amount_after_transfer_fee, require_approved_transfer_hook, transfer_tokens,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface;
-use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface, TransferChecked};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 // Deposit SPL tokens into a testudo account. authority is the sender and Centurion is the receiver.
 
@@ -37,6 +42,7 @@ pub struct WithdrawSplToken<'info> {
         seeds = [b"centurion".as_ref(), authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
@@ -64,6 +70,7 @@ pub struct WithdrawSplToken<'info> {
         seeds = [b"legate"],
         bump = legate.bump,
         constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
     )]
     pub legate: Account<'info, Legate>,
 
@@ -98,8 +105,8 @@ pub struct WithdrawSplToken<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
-pub fn process_withdraw_spl_token(
-    ctx: Context<WithdrawSplToken>,
+pub fn process_withdraw_spl_token<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawSplToken<'info>>,
     amount_in_decimals: u64,
 ) -> Result<()> {
     let centurion_data: &mut Account<'_, Centurion> = &mut ctx.accounts.centurion;
@@ -115,6 +122,9 @@ pub fn process_withdraw_spl_token(
         InvalidTokenMint
     );
 
+    // Hooked mints must still point at the hook program approved on their whitelist entry
+    require_approved_transfer_hook(&ctx.accounts.legate, &ctx.accounts.mint)?;
+
     // Get the amount of tokens the depositor has in their ATA
     let testudo_token_holdings: u64 = ctx.accounts.testudo.amount;
 
@@ -125,66 +135,56 @@ pub fn process_withdraw_spl_token(
         InsufficientFunds
     );
 
-    let withdraw_fee = amount_in_decimals
-        .checked_mul(ctx.accounts.legate.percent_for_fees as u64)
-        .unwrap_or(0)
-        .checked_div(10000)
-        .unwrap_or(0);
+    // The protocol fee is charged on what actually leaves, after the mint's transfer fee
+    let withdraw_fee = calculate_fee(
+        &ctx.accounts.legate,
+        FeeOperation::Withdraw,
+        &ctx.accounts.mint.key(),
+        amount_after_transfer_fee(&ctx.accounts.mint, amount_in_decimals)?,
+        ctx.accounts.mint.decimals,
+    )?;
     let amount_after_fee = amount_in_decimals
         .checked_sub(withdraw_fee)
         .ok_or(ArithmeticOverflow)?;
 
-    // Get the testudo account for the token
-    let testudo_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.testudo;
-    // Get the depositor's ATA for the token
-    let authority_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.authority_ata;
-    // Get the treasury ATA for the token
-    let treasury_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.treasury_ata;
-
-    // Get the number of decimals for the token
-    let decimals: u8 = ctx.accounts.mint.decimals;
-
     let signer_seeds: &[&[&[u8]]] = &[&[
         b"centurion",
         ctx.accounts.authority.key.as_ref(),
         &[ctx.bumps.centurion],
     ]];
 
-    // Set up the CPI accounts for the transfer of fee
-    let cpi_accounts_for_fee = TransferChecked {
-        from: testudo_ata.to_account_info(),
-        to: treasury_ata.to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        authority: centurion_data.to_account_info(),
-    };
-
-    // Set up the CPI context for the transfer of fee
-    let cpi_context_for_fee = CpiContext::new_with_signer(
+    // Transfer the fee to the treasury
+    transfer_tokens(
         ctx.accounts.token_program.to_account_info(),
-        cpi_accounts_for_fee,
+        ctx.accounts.testudo.to_account_info(),
+        ctx.accounts.treasury_ata.to_account_info(),
+        centurion_data.to_account_info(),
+        &ctx.accounts.mint,
+        withdraw_fee,
         signer_seeds,
-    );
-
-    // Perform the transfer
-    token_interface::transfer_checked(cpi_context_for_fee, withdraw_fee, decimals)?;
-
-    // Set up the CPI accounts for the transfer of amount after fee
-    let cpi_accounts_for_withdraw = TransferChecked {
-        from: testudo_ata.to_account_info(),
-        to: authority_ata.to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        authority: centurion_data.to_account_info(),
-    };
+        ctx.remaining_accounts,
+    )?;
 
-    // Set up the CPI context for the transfer of amount after fee
-    let cpi_context_for_withdraw = CpiContext::new_with_signer(
+    // Transfer the amount after fee to the authority
+    let amount_received = transfer_tokens(
         ctx.accounts.token_program.to_account_info(),
-        cpi_accounts_for_withdraw,
+        ctx.accounts.testudo.to_account_info(),
+        ctx.accounts.authority_ata.to_account_info(),
+        centurion_data.to_account_info(),
+        &ctx.accounts.mint,
+        amount_after_fee,
         signer_seeds,
-    );
-
-    // Perform the transfer
-    token_interface::transfer_checked(cpi_context_for_withdraw, amount_after_fee, decimals)?;
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(SplWithdrawn {
+        centurion: centurion_data.key(),
+        mint: ctx.accounts.mint.key(),
+        recipient: ctx.accounts.authority_ata.key(),
+        amount: amount_in_decimals,
+        protocol_fee: withdraw_fee,
+        amount_received,
+    });
 
     // Update the last accessed timestamp
     let current_datetime: i64 = Clock::get()?.unix_timestamp;
@@ -1,15 +1,19 @@
 use crate::custom_accounts::centurion::*;
-use crate::custom_accounts::legate::Legate;
+use crate::custom_accounts::legate::{FeeOperation, Legate};
 use crate::errors::ErrorCode::{
-    ArithmeticOverflow, CenturionNotInitialized, InvalidATA, InvalidAuthority,
-    InvalidBackupAccount, InvalidPasswordSignature, InvalidTokenMint, InvalidTreasuryAccount,
-    LegateNotInitialized, NoBackupAccountStored,
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InvalidATA,
+    InvalidAuthority, InvalidBackupAccount, InvalidPasswordSignature, InvalidTokenMint,
+    InvalidTreasuryAccount, LegateNotInitialized, LegateNotMigrated, NoBackupAccountStored,
+    YieldPositionsOpen,
+};
+use crate::events::SplWithdrawn;
+use crate::fees::calculate_fee;
+use crate::transfers::{
+    amount_after_transfer_fee, require_approved_transfer_hook, transfer_tokens,
 };
 use anchor_lang::prelude::*;
 use anchor_spl::associated_token::AssociatedToken;
-use anchor_spl::token_interface::{
-    transfer_checked, Mint, TokenAccount, TokenInterface, TransferChecked,
-};
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
 
 // Withdraw tokens to the backup account
 
@@ -30,6 +34,7 @@ pub struct WithdrawToBackup<'info> {
         seeds = [b"centurion", authority.key.as_ref()],
         bump,
         constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
         has_one = authority @InvalidAuthority,
     )]
     pub centurion: Account<'info, Centurion>,
@@ -80,6 +85,7 @@ pub struct WithdrawToBackup<'info> {
             seeds = [b"legate"],
             bump = legate.bump,
             constraint = legate.is_initialized @LegateNotInitialized,
+            constraint = legate.is_migrated() @LegateNotMigrated,
         )]
     pub legate: Account<'info, Legate>,
 
@@ -112,7 +118,9 @@ pub struct WithdrawToBackup<'info> {
     pub associated_token_program: Program<'info, AssociatedToken>,
 }
 
-pub fn process_withdraw_to_backup(ctx: Context<WithdrawToBackup>) -> Result<()> {
+pub fn process_withdraw_to_backup<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawToBackup<'info>>,
+) -> Result<()> {
     let centurion_data: &mut Account<'_, Centurion> = &mut ctx.accounts.centurion;
     let centurion_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.testudo;
     let backup_ata: &mut InterfaceAccount<'_, TokenAccount> = &mut ctx.accounts.backup_ata;
@@ -128,6 +136,16 @@ pub fn process_withdraw_to_backup(ctx: Context<WithdrawToBackup>) -> Result<()>
         InvalidTokenMint
     );
 
+    // Yield positions in this mint are unwound to the backup account first, by withdraw_yield_position_to_backup
+    require_eq!(
+        centurion_data.has_yield_position_in(&ctx.accounts.mint.key()),
+        false,
+        YieldPositionsOpen
+    );
+
+    // Hooked mints must still point at the hook program approved on their whitelist entry
+    require_approved_transfer_hook(&ctx.accounts.legate, &ctx.accounts.mint)?;
+
     // (double check) Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
     require_eq!(
         password_pubkey,
@@ -142,65 +160,58 @@ pub fn process_withdraw_to_backup(ctx: Context<WithdrawToBackup>) -> Result<()>
         InvalidBackupAccount
     );
 
-    let withdraw_fee = centurion_ata
-        .amount
-        .checked_mul(ctx.accounts.legate.percent_for_fees as u64)
-        .unwrap_or(0)
-        .checked_div(10000)
-        .unwrap_or(0);
-    let amount_after_fee = centurion_ata
-        .amount
+    // The protocol fee is charged on what actually leaves, after the mint's transfer fee
+    let amount_in_decimals = centurion_ata.amount;
+    let withdraw_fee = calculate_fee(
+        &ctx.accounts.legate,
+        FeeOperation::BackupRecovery,
+        &ctx.accounts.mint.key(),
+        amount_after_transfer_fee(&ctx.accounts.mint, amount_in_decimals)?,
+        ctx.accounts.mint.decimals,
+    )?;
+    let amount_after_fee = amount_in_decimals
         .checked_sub(withdraw_fee)
         .ok_or(ArithmeticOverflow)?;
 
-    let token_program: &mut Interface<'_, TokenInterface> = &mut ctx.accounts.token_program;
-
     let signer_seeds: &[&[&[u8]]] = &[&[
         b"centurion",
         ctx.accounts.authority.key.as_ref(),
         &[ctx.bumps.centurion],
     ]];
 
-    // Set up the CPI accounts for the transfer of fee
-    let cpi_accounts_for_fee = TransferChecked {
-        from: centurion_ata.to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        to: ctx.accounts.treasury_ata.to_account_info(),
-        authority: centurion_data.to_account_info(),
-    };
-
-    let cpi_context_for_fee = CpiContext::new_with_signer(
-        token_program.to_account_info(),
-        cpi_accounts_for_fee,
-        signer_seeds,
-    );
-
-    transfer_checked(
-        cpi_context_for_fee,
+    // Transfer the fee to the treasury
+    transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        centurion_ata.to_account_info(),
+        ctx.accounts.treasury_ata.to_account_info(),
+        centurion_data.to_account_info(),
+        &ctx.accounts.mint,
         withdraw_fee,
-        ctx.accounts.mint.decimals,
-    )?;
-
-    // Set up the CPI accounts for the transfer of amount after fee
-    let cpi_accounts_for_withdraw = TransferChecked {
-        from: centurion_ata.to_account_info(),
-        mint: ctx.accounts.mint.to_account_info(),
-        to: backup_ata.to_account_info(),
-        authority: centurion_data.to_account_info(),
-    };
-
-    let cpi_context_for_withdraw = CpiContext::new_with_signer(
-        token_program.to_account_info(),
-        cpi_accounts_for_withdraw,
         signer_seeds,
-    );
+        ctx.remaining_accounts,
+    )?;
 
-    transfer_checked(
-        cpi_context_for_withdraw,
+    // Transfer the amount after fee to the backup account
+    let amount_received = transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        centurion_ata.to_account_info(),
+        backup_ata.to_account_info(),
+        centurion_data.to_account_info(),
+        &ctx.accounts.mint,
         amount_after_fee,
-        ctx.accounts.mint.decimals,
+        signer_seeds,
+        ctx.remaining_accounts,
     )?;
 
+    emit!(SplWithdrawn {
+        centurion: centurion_data.key(),
+        mint: ctx.accounts.mint.key(),
+        recipient: backup_ata.key(),
+        amount: amount_in_decimals,
+        protocol_fee: withdraw_fee,
+        amount_received,
+    });
+
     // Update the last accessed timestamp
     let current_datetime: i64 = Clock::get()?.unix_timestamp;
     centurion_data.last_accessed = current_datetime as u64;
@@ -0,0 +1,186 @@
+use crate::constants::MAX_YIELD_POSITIONS_PER_CENTURION;
+use crate::custom_accounts::centurion::{Centurion, YieldPosition};
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds,
+    InvalidAuthority, InvalidPasswordSignature, InvalidYieldAmount, LegateNotInitialized,
+    LegateNotMigrated, MaxYieldPositionsReached, UnapprovedYieldProgram, YieldAdapterMisbehaved,
+    YieldPositionMismatch,
+};
+use crate::events::YieldPositionEntered;
+use crate::yield_adapter::{
+    invoke_yield_adapter, require_untouched_authorities, YieldAdapterAccounts,
+    YIELD_DEPOSIT_DISCRIMINATOR,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+// Deploy part of a testudo into a Legate-approved yield program. The position tokens are minted to a
+// Centurion-owned account, seeded by the position mint.
+
+#[derive(Accounts)]
+pub struct EnterYieldPosition<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+        constraint = legate.yield_programs.contains(&adapter_program.key()) @UnapprovedYieldProgram,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // YIELD ADAPTER
+    /// CHECK: Approved by the Legate
+    pub adapter_program: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the adapter
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the adapter
+    pub vault_reserve: UncheckedAccount<'info>,
+
+    // TESTUDO TOKEN ACCOUNT
+    #[account(
+        mut,
+        seeds = [centurion.key().as_ref(), underlying_mint.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+    )]
+    pub testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // POSITION TOKEN ACCOUNT
+    #[account(
+        init_if_needed,
+        payer = authority,
+        seeds = [centurion.key().as_ref(), position_mint.key().as_ref(), b"yield".as_ref()],
+        bump,
+        token::mint = position_mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+    )]
+    pub position_account: InterfaceAccount<'info, TokenAccount>,
+
+    // MINTS
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        constraint = position_mint.key() != underlying_mint.key() @YieldPositionMismatch,
+    )]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    // PROGRAMS
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_enter_yield_position(ctx: Context<EnterYieldPosition>, amount: u64) -> Result<()> {
+    require_gt!(amount, 0, InvalidYieldAmount);
+    require_gte!(ctx.accounts.testudo.amount, amount, InsufficientFunds);
+
+    let position = YieldPosition {
+        adapter_program: ctx.accounts.adapter_program.key(),
+        vault: ctx.accounts.vault.key(),
+        underlying_mint: ctx.accounts.underlying_mint.key(),
+        position_mint: ctx.accounts.position_mint.key(),
+    };
+
+    // Topping up an open position must go through the same program and mints it was opened with
+    let centurion = &mut ctx.accounts.centurion;
+    match centurion
+        .yield_positions
+        .iter()
+        .find(|open| open.vault == position.vault)
+    {
+        Some(open) => require_eq!((open == &position), true, YieldPositionMismatch),
+        None => {
+            require_gt!(
+                MAX_YIELD_POSITIONS_PER_CENTURION,
+                centurion.yield_positions.len(),
+                MaxYieldPositionsReached
+            );
+            centurion.yield_positions.push(position.clone());
+        }
+    }
+
+    let underlying_before = ctx.accounts.testudo.amount;
+    let shares_before = ctx.accounts.position_account.amount;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    invoke_yield_adapter(
+        YieldAdapterAccounts {
+            adapter_program: ctx.accounts.adapter_program.to_account_info(),
+            vault: ctx.accounts.vault.to_account_info(),
+            underlying_mint: ctx.accounts.underlying_mint.to_account_info(),
+            position_mint: ctx.accounts.position_mint.to_account_info(),
+            vault_reserve: ctx.accounts.vault_reserve.to_account_info(),
+            owner_underlying: ctx.accounts.testudo.to_account_info(),
+            owner_position: ctx.accounts.position_account.to_account_info(),
+            owner: ctx.accounts.centurion.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        YIELD_DEPOSIT_DISCRIMINATOR,
+        amount,
+        signer_seeds,
+    )?;
+
+    // The adapter takes exactly `amount`, credits shares, and leaves both accounts fully under the Centurion
+    ctx.accounts.testudo.reload()?;
+    ctx.accounts.position_account.reload()?;
+    let spent = underlying_before
+        .checked_sub(ctx.accounts.testudo.amount)
+        .ok_or(YieldAdapterMisbehaved)?;
+    require_eq!(spent, amount, YieldAdapterMisbehaved);
+    let shares = ctx
+        .accounts
+        .position_account
+        .amount
+        .checked_sub(shares_before)
+        .ok_or(ArithmeticOverflow)?;
+    require_gt!(shares, 0, YieldAdapterMisbehaved);
+    require_untouched_authorities(&ctx.accounts.testudo, &ctx.accounts.centurion.key())?;
+    require_untouched_authorities(
+        &ctx.accounts.position_account,
+        &ctx.accounts.centurion.key(),
+    )?;
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    emit!(YieldPositionEntered {
+        centurion: ctx.accounts.centurion.key(),
+        adapter_program: position.adapter_program,
+        vault: position.vault,
+        underlying_mint: position.underlying_mint,
+        amount,
+        shares,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,153 @@
+use crate::custom_accounts::centurion::{Centurion, YieldPosition};
+use crate::errors::ErrorCode::{
+    CenturionNotInitialized, CenturionNotMigrated, InsufficientFunds, InvalidAuthority,
+    InvalidPasswordSignature, InvalidYieldAmount, YieldPositionNotFound,
+};
+use crate::events::YieldPositionExited;
+use crate::yield_adapter::{redeem_position_shares, YieldAdapterAccounts};
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+
+// Redeem position tokens for the underlying, paid back into the testudo. Exiting the whole position closes the
+// position token account and drops the position. Works even after the Legate revokes the yield program.
+
+#[derive(Accounts)]
+pub struct ExitYieldPosition<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // YIELD ADAPTER
+    /// CHECK: Must be the program the position was opened with
+    pub adapter_program: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Must be the vault the position was opened in
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the adapter
+    pub vault_reserve: UncheckedAccount<'info>,
+
+    // TESTUDO TOKEN ACCOUNT
+    #[account(
+        mut,
+        seeds = [centurion.key().as_ref(), underlying_mint.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+    )]
+    pub testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // POSITION TOKEN ACCOUNT
+    #[account(
+        mut,
+        seeds = [centurion.key().as_ref(), position_mint.key().as_ref(), b"yield".as_ref()],
+        bump,
+        token::mint = position_mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+    )]
+    pub position_account: InterfaceAccount<'info, TokenAccount>,
+
+    // MINTS
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    // PROGRAMS
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+pub fn process_exit_yield_position(ctx: Context<ExitYieldPosition>, shares: u64) -> Result<()> {
+    require_gt!(shares, 0, InvalidYieldAmount);
+    require_gte!(
+        ctx.accounts.position_account.amount,
+        shares,
+        InsufficientFunds
+    );
+
+    let position = YieldPosition {
+        adapter_program: ctx.accounts.adapter_program.key(),
+        vault: ctx.accounts.vault.key(),
+        underlying_mint: ctx.accounts.underlying_mint.key(),
+        position_mint: ctx.accounts.position_mint.key(),
+    };
+    require_eq!(
+        ctx.accounts.centurion.yield_positions.contains(&position),
+        true,
+        YieldPositionNotFound
+    );
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    let amount_received = redeem_position_shares(
+        YieldAdapterAccounts {
+            adapter_program: ctx.accounts.adapter_program.to_account_info(),
+            vault: ctx.accounts.vault.to_account_info(),
+            underlying_mint: ctx.accounts.underlying_mint.to_account_info(),
+            position_mint: ctx.accounts.position_mint.to_account_info(),
+            vault_reserve: ctx.accounts.vault_reserve.to_account_info(),
+            owner_underlying: ctx.accounts.testudo.to_account_info(),
+            owner_position: ctx.accounts.position_account.to_account_info(),
+            owner: ctx.accounts.centurion.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        &mut ctx.accounts.testudo,
+        &mut ctx.accounts.position_account,
+        shares,
+        signer_seeds,
+    )?;
+
+    let closed = ctx.accounts.position_account.amount == 0;
+    if closed {
+        close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.position_account.to_account_info(),
+                destination: ctx.accounts.authority.to_account_info(),
+                authority: ctx.accounts.centurion.to_account_info(),
+            },
+            signer_seeds,
+        ))?;
+        ctx.accounts
+            .centurion
+            .yield_positions
+            .retain(|open| *open != position);
+    }
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    emit!(YieldPositionExited {
+        centurion: ctx.accounts.centurion.key(),
+        adapter_program: position.adapter_program,
+        vault: position.vault,
+        underlying_mint: position.underlying_mint,
+        shares,
+        amount_received,
+        closed,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,5 @@
+pub mod enter_yield_position;
+
+pub mod exit_yield_position;
+
+pub mod withdraw_yield_position_to_backup;
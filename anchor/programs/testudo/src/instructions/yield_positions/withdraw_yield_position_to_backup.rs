@@ -0,0 +1,249 @@
+use crate::custom_accounts::centurion::{Centurion, YieldPosition};
+use crate::custom_accounts::legate::{FeeOperation, Legate};
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, CenturionNotInitialized, CenturionNotMigrated, InvalidAuthority,
+    InvalidBackupAccount, InvalidPasswordSignature, InvalidTreasuryAccount, LegateNotInitialized,
+    LegateNotMigrated, NoBackupAccountStored, YieldPositionNotFound,
+};
+use crate::events::{SplWithdrawn, YieldPositionExited};
+use crate::fees::calculate_fee;
+use crate::transfers::{
+    amount_after_transfer_fee, require_approved_transfer_hook, transfer_tokens,
+};
+use crate::yield_adapter::{redeem_position_shares, YieldAdapterAccounts};
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{
+    close_account, CloseAccount, Mint, TokenAccount, TokenInterface,
+};
+
+// Unwind a whole yield position through its adapter and sweep what it redeems for to the backup account.
+// The position token account is closed and the position dropped, so the testudo can be swept after it.
+// Works even after the Legate revokes the yield program.
+
+#[derive(Accounts)]
+pub struct WithdrawYieldPositionToBackup<'info> {
+    // SIGNERS
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(
+        // Ensure the pubkey of the signer is the same as the pubkey of the password (stored in the centurion account)
+        constraint = centurion.pubkey_to_password == valid_signer_of_password.key() @InvalidPasswordSignature
+    )]
+    pub valid_signer_of_password: Signer<'info>,
+
+    // CENTURION
+    #[account(
+        mut,
+        seeds = [b"centurion".as_ref(), authority.key.as_ref()],
+        bump,
+        constraint = centurion.is_initialized @CenturionNotInitialized,
+        constraint = centurion.is_migrated() @CenturionNotMigrated,
+        has_one = authority @InvalidAuthority,
+    )]
+    pub centurion: Account<'info, Centurion>,
+
+    // YIELD ADAPTER
+    /// CHECK: Must be the program the position was opened with
+    pub adapter_program: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Must be the vault the position was opened in
+    pub vault: UncheckedAccount<'info>,
+    #[account(mut)]
+    /// CHECK: Validated by the adapter
+    pub vault_reserve: UncheckedAccount<'info>,
+
+    // TESTUDO TOKEN ACCOUNT. Receives the redeemed underlying on its way to the backup account
+    #[account(
+        mut,
+        seeds = [centurion.key().as_ref(), underlying_mint.key().as_ref()],
+        bump,
+        token::mint = underlying_mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+    )]
+    pub testudo: InterfaceAccount<'info, TokenAccount>,
+
+    // POSITION TOKEN ACCOUNT
+    #[account(
+        mut,
+        seeds = [centurion.key().as_ref(), position_mint.key().as_ref(), b"yield".as_ref()],
+        bump,
+        token::mint = position_mint,
+        token::authority = centurion,
+        token::token_program = token_program,
+    )]
+    pub position_account: InterfaceAccount<'info, TokenAccount>,
+
+    // BACKUP ACCOUNT
+    #[account(
+        // Ensure backup account provided matches account saved in Centurion
+        constraint = backup_account.key() == centurion.backup_owner.ok_or(NoBackupAccountStored)? @InvalidBackupAccount
+    )]
+    /// CHECK: Explicit wrapper for AccountInfo type to emphasize that no checks are performed
+    pub backup_account: UncheckedAccount<'info>,
+
+    // BACKUP ATA (UNDERLYING MINT)
+    #[account(
+        init_if_needed,
+        payer = authority,
+        associated_token::mint = underlying_mint,
+        associated_token::authority = backup_account,
+        associated_token::token_program = token_program,
+    )]
+    pub backup_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // MINTS
+    pub underlying_mint: InterfaceAccount<'info, Mint>,
+    #[account(mut)]
+    pub position_mint: InterfaceAccount<'info, Mint>,
+
+    // LEGATE
+    #[account(
+        seeds = [b"legate"],
+        bump = legate.bump,
+        constraint = legate.is_initialized @LegateNotInitialized,
+        constraint = legate.is_migrated() @LegateNotMigrated,
+    )]
+    pub legate: Account<'info, Legate>,
+
+    // TREASURY
+    #[account(
+        constraint = legate.treasury_acc == treasury.key() @InvalidTreasuryAccount
+    )]
+    /// CHECK: Explicit wrapper for AccountInfo type to emphasize that no checks are performed
+    pub treasury: UncheckedAccount<'info>,
+
+    // TREASURY ATA (UNDERLYING MINT)
+    #[account(
+        mut,
+        associated_token::mint = underlying_mint,
+        associated_token::authority = treasury,
+        associated_token::token_program = token_program,
+    )]
+    pub treasury_ata: InterfaceAccount<'info, TokenAccount>,
+
+    // PROGRAMS
+    #[account(
+        constraint = token_program.key() == anchor_spl::token::ID || token_program.key() == anchor_spl::token_2022::ID
+    )]
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn process_withdraw_yield_position_to_backup<'info>(
+    ctx: Context<'_, '_, '_, 'info, WithdrawYieldPositionToBackup<'info>>,
+) -> Result<()> {
+    let position = YieldPosition {
+        adapter_program: ctx.accounts.adapter_program.key(),
+        vault: ctx.accounts.vault.key(),
+        underlying_mint: ctx.accounts.underlying_mint.key(),
+        position_mint: ctx.accounts.position_mint.key(),
+    };
+    require_eq!(
+        ctx.accounts.centurion.yield_positions.contains(&position),
+        true,
+        YieldPositionNotFound
+    );
+
+    // Hooked mints must still point at the hook program approved on their whitelist entry
+    require_approved_transfer_hook(&ctx.accounts.legate, &ctx.accounts.underlying_mint)?;
+
+    let signer_seeds: &[&[&[u8]]] = &[&[
+        b"centurion",
+        ctx.accounts.authority.key.as_ref(),
+        &[ctx.bumps.centurion],
+    ]];
+
+    // Redeem every share of the position into the testudo
+    let shares = ctx.accounts.position_account.amount;
+    let amount_redeemed = redeem_position_shares(
+        YieldAdapterAccounts {
+            adapter_program: ctx.accounts.adapter_program.to_account_info(),
+            vault: ctx.accounts.vault.to_account_info(),
+            underlying_mint: ctx.accounts.underlying_mint.to_account_info(),
+            position_mint: ctx.accounts.position_mint.to_account_info(),
+            vault_reserve: ctx.accounts.vault_reserve.to_account_info(),
+            owner_underlying: ctx.accounts.testudo.to_account_info(),
+            owner_position: ctx.accounts.position_account.to_account_info(),
+            owner: ctx.accounts.centurion.to_account_info(),
+            token_program: ctx.accounts.token_program.to_account_info(),
+        },
+        &mut ctx.accounts.testudo,
+        &mut ctx.accounts.position_account,
+        shares,
+        signer_seeds,
+    )?;
+
+    close_account(CpiContext::new_with_signer(
+        ctx.accounts.token_program.to_account_info(),
+        CloseAccount {
+            account: ctx.accounts.position_account.to_account_info(),
+            destination: ctx.accounts.authority.to_account_info(),
+            authority: ctx.accounts.centurion.to_account_info(),
+        },
+        signer_seeds,
+    ))?;
+    ctx.accounts
+        .centurion
+        .yield_positions
+        .retain(|open| *open != position);
+
+    emit!(YieldPositionExited {
+        centurion: ctx.accounts.centurion.key(),
+        adapter_program: position.adapter_program,
+        vault: position.vault,
+        underlying_mint: position.underlying_mint,
+        shares,
+        amount_received: amount_redeemed,
+        closed: true,
+    });
+
+    // The protocol fee is charged on what actually leaves, after the mint's transfer fee
+    let withdraw_fee = calculate_fee(
+        &ctx.accounts.legate,
+        FeeOperation::BackupRecovery,
+        &ctx.accounts.underlying_mint.key(),
+        amount_after_transfer_fee(&ctx.accounts.underlying_mint, amount_redeemed)?,
+        ctx.accounts.underlying_mint.decimals,
+    )?;
+    let amount_after_fee = amount_redeemed
+        .checked_sub(withdraw_fee)
+        .ok_or(ArithmeticOverflow)?;
+
+    // Hooked mints need their extra accounts in remaining_accounts
+    transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.testudo.to_account_info(),
+        ctx.accounts.treasury_ata.to_account_info(),
+        ctx.accounts.centurion.to_account_info(),
+        &ctx.accounts.underlying_mint,
+        withdraw_fee,
+        signer_seeds,
+        ctx.remaining_accounts,
+    )?;
+    let amount_received = transfer_tokens(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.testudo.to_account_info(),
+        ctx.accounts.backup_ata.to_account_info(),
+        ctx.accounts.centurion.to_account_info(),
+        &ctx.accounts.underlying_mint,
+        amount_after_fee,
+        signer_seeds,
+        ctx.remaining_accounts,
+    )?;
+
+    emit!(SplWithdrawn {
+        centurion: ctx.accounts.centurion.key(),
+        mint: ctx.accounts.underlying_mint.key(),
+        recipient: ctx.accounts.backup_ata.key(),
+        amount: amount_redeemed,
+        protocol_fee: withdraw_fee,
+        amount_received,
+    });
+
+    ctx.accounts.centurion.last_accessed = Clock::get()?.unix_timestamp as u64;
+
+    Ok(())
+}
@@ -1,9 +1,17 @@
 use anchor_lang::prelude::*;
 use instructions::*;
 
+mod constants;
 mod custom_accounts;
 mod errors;
+mod events;
+mod fees;
 mod instructions;
+mod metadata;
+mod mint_risk;
+mod oracle;
+mod transfers;
+mod yield_adapter;
 
 declare_id!("nikxjF5jnkFtoGTdQdQoqBWvoP4nFGbJhMHtVKZMnbL");
 
@@ -19,6 +27,10 @@ pub mod testudo {
         process_close_legate(ctx)
     }
 
+    pub fn migrate_legate(ctx: Context<MigrateLegate>) -> Result<()> {
+        instructions::process_migrate_legate(ctx)
+    }
+
     pub fn init_centurion(
         ctx: Context<InitCenturion>,
         password_pubkey: Pubkey,
@@ -31,11 +43,17 @@ pub mod testudo {
         instructions::process_close_centurion(ctx)
     }
 
+    pub fn migrate_centurion(ctx: Context<MigrateCenturion>) -> Result<()> {
+        instructions::process_migrate_centurion(ctx)
+    }
+
     pub fn init_testudo(ctx: Context<InitTestudo>) -> Result<()> {
         instructions::process_init_testudo(ctx)
     }
 
-    pub fn close_testudo(ctx: Context<CloseTestudo>) -> Result<()> {
+    pub fn close_testudo<'info>(
+        ctx: Context<'_, '_, '_, 'info, CloseTestudo<'info>>,
+    ) -> Result<()> {
         instructions::process_close_testudo(ctx)
     }
 
@@ -43,7 +61,41 @@ pub mod testudo {
         instructions::process_deposit_sol(ctx, amount_in_lamports)
     }
 
-    pub fn deposit_spl(ctx: Context<DepositSplToken>, amount_with_decimals: u64) -> Result<()> {
+    pub fn deposit_sol_to_wsol(
+        ctx: Context<DepositSolToWsol>,
+        amount_in_lamports: u64,
+    ) -> Result<()> {
+        instructions::process_deposit_sol_to_wsol(ctx, amount_in_lamports)
+    }
+
+    pub fn deposit_sol_for(
+        ctx: Context<DepositSolFor>,
+        amount_in_lamports: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        instructions::process_deposit_sol_for(ctx, amount_in_lamports, memo)
+    }
+
+    pub fn deposit_spl_for<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositSplTokenFor<'info>>,
+        amount_with_decimals: u64,
+        memo: Option<String>,
+    ) -> Result<()> {
+        instructions::process_deposit_spl_token_for(ctx, amount_with_decimals, memo)
+    }
+
+    pub fn wrap_sol(ctx: Context<WrapSol>, amount_in_lamports: u64) -> Result<()> {
+        instructions::process_wrap_sol(ctx, amount_in_lamports)
+    }
+
+    pub fn unwrap_sol(ctx: Context<UnwrapSol>, amount_in_lamports: u64) -> Result<()> {
+        instructions::process_unwrap_sol(ctx, amount_in_lamports)
+    }
+
+    pub fn deposit_spl<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositSplToken<'info>>,
+        amount_with_decimals: u64,
+    ) -> Result<()> {
         instructions::process_deposit_spl_token(ctx, amount_with_decimals)
     }
 
@@ -51,11 +103,22 @@ pub mod testudo {
         instructions::process_withdraw_sol(ctx, amount_in_lamports)
     }
 
-    pub fn withdraw_spl(ctx: Context<WithdrawSplToken>, amount_with_decimals: u64) -> Result<()> {
+    pub fn withdraw_spl<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawSplToken<'info>>,
+        amount_with_decimals: u64,
+    ) -> Result<()> {
         instructions::process_withdraw_spl_token(ctx, amount_with_decimals)
     }
 
-    pub fn withdraw_to_backup(ctx: Context<WithdrawToBackup>) -> Result<()> {
+    pub fn rescue_tokens<'info>(
+        ctx: Context<'_, '_, '_, 'info, RescueTokens<'info>>,
+    ) -> Result<()> {
+        instructions::process_rescue_tokens(ctx)
+    }
+
+    pub fn withdraw_to_backup<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawToBackup<'info>>,
+    ) -> Result<()> {
         instructions::process_withdraw_to_backup(ctx)
     }
 
@@ -95,6 +158,17 @@ pub mod testudo {
         instructions::process_update_back_up_account(ctx, backup_account)
     }
 
+    pub fn reconcile_centurion(ctx: Context<ReconcileCenturion>) -> Result<()> {
+        instructions::process_reconcile_centurion(ctx)
+    }
+
+    pub fn set_third_party_deposits(
+        ctx: Context<SetThirdPartyDeposits>,
+        enabled: bool,
+    ) -> Result<()> {
+        instructions::process_set_third_party_deposits(ctx, enabled)
+    }
+
     pub fn update_treasury(ctx: Context<UpdateTreasury>, new_treasury: Pubkey) -> Result<()> {
         instructions::process_update_treasury(ctx, new_treasury)
     }
@@ -103,19 +177,331 @@ pub mod testudo {
         instructions::process_update_fee_percent(ctx, new_fee_percent)
     }
 
-    pub fn swap(
-        ctx: Context<Swap>,
-        jupiter_swap: instructions::swaps::swap::JupiterInstructionWithIdxs,
-        jupiter_setup: Vec<instructions::swaps::swap::JupiterInstructionWithIdxs>, // This involves SOL -> WSOL version and other accounts setup
-        jupiter_cleanup: instructions::swaps::swap::JupiterInstructionWithIdxs, // Idxs to clean up accounts etc post swap
-        testudo_data: Vec<custom_accounts::centurion::TestudoData>,
+    pub fn update_fee_tiers(
+        ctx: Context<UpdateFeeTiers>,
+        new_fee_tiers: Vec<custom_accounts::legate::FeeTier>,
+        new_fee_tier_mode: custom_accounts::legate::FeeTierMode,
+    ) -> Result<()> {
+        instructions::process_update_fee_tiers(ctx, new_fee_tiers, new_fee_tier_mode)
+    }
+
+    pub fn update_mint_fee_percent(
+        ctx: Context<UpdateMintFeePercent>,
+        mint: Pubkey,
+        new_fee_percent: Option<u16>,
+    ) -> Result<()> {
+        instructions::process_update_mint_fee_percent(ctx, mint, new_fee_percent)
+    }
+
+    pub fn update_mint_fee_limits(
+        ctx: Context<UpdateMintFeeLimits>,
+        mint: Pubkey,
+        new_min_fee: u64,
+        new_flat_fee: u64,
+    ) -> Result<()> {
+        instructions::process_update_mint_fee_limits(ctx, mint, new_min_fee, new_flat_fee)
+    }
+
+    pub fn update_sol_fee_limits(
+        ctx: Context<UpdateSolFeeLimits>,
+        new_min_fee_lamports: u64,
+        new_flat_fee_lamports: u64,
+    ) -> Result<()> {
+        instructions::process_update_sol_fee_limits(
+            ctx,
+            new_min_fee_lamports,
+            new_flat_fee_lamports,
+        )
+    }
+
+    pub fn update_fee_rounding(ctx: Context<UpdateFeeRounding>, round_fees_up: bool) -> Result<()> {
+        instructions::process_update_fee_rounding(ctx, round_fees_up)
+    }
+
+    pub fn update_operation_fee(
+        ctx: Context<UpdateOperationFee>,
+        operation: custom_accounts::legate::FeeOperation,
+        new_operation_fee: custom_accounts::legate::OperationFee,
+    ) -> Result<()> {
+        instructions::process_update_operation_fee(ctx, operation, new_operation_fee)
+    }
+
+    pub fn add_approved_cpi_program(
+        ctx: Context<AddApprovedCpiProgram>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        instructions::process_add_approved_cpi_program(ctx, program_id)
+    }
+
+    pub fn remove_approved_cpi_program(
+        ctx: Context<RemoveApprovedCpiProgram>,
+        program_id: Pubkey,
+    ) -> Result<()> {
+        instructions::process_remove_approved_cpi_program(ctx, program_id)
+    }
+
+    pub fn add_swap_router(ctx: Context<AddSwapRouter>, router: Pubkey) -> Result<()> {
+        instructions::process_add_swap_router(ctx, router)
+    }
+
+    pub fn remove_swap_router(ctx: Context<RemoveSwapRouter>, router: Pubkey) -> Result<()> {
+        instructions::process_remove_swap_router(ctx, router)
+    }
+
+    pub fn add_nft_collection(ctx: Context<AddNftCollection>, collection: Pubkey) -> Result<()> {
+        instructions::process_add_nft_collection(ctx, collection)
+    }
+
+    pub fn remove_nft_collection(
+        ctx: Context<RemoveNftCollection>,
+        collection: Pubkey,
+    ) -> Result<()> {
+        instructions::process_remove_nft_collection(ctx, collection)
+    }
+
+    pub fn deposit_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, DepositNft<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::process_deposit_nft(ctx, amount)
+    }
+
+    pub fn withdraw_nft<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawNft<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::process_withdraw_nft(ctx, amount)
+    }
+
+    pub fn withdraw_nft_to_backup<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawNftToBackup<'info>>,
+    ) -> Result<()> {
+        instructions::process_withdraw_nft_to_backup(ctx)
+    }
+
+    pub fn create_stake_account(
+        ctx: Context<CreateStakeAccount>,
+        stake_index: u8,
+        amount_in_lamports: u64,
+    ) -> Result<()> {
+        instructions::process_create_stake_account(ctx, stake_index, amount_in_lamports)
+    }
+
+    pub fn delegate_stake(ctx: Context<DelegateStake>) -> Result<()> {
+        instructions::process_delegate_stake(ctx)
+    }
+
+    pub fn deactivate_stake(ctx: Context<DeactivateStake>) -> Result<()> {
+        instructions::process_deactivate_stake(ctx)
+    }
+
+    pub fn withdraw_stake(ctx: Context<WithdrawStake>, amount_in_lamports: u64) -> Result<()> {
+        instructions::process_withdraw_stake(ctx, amount_in_lamports)
+    }
+
+    pub fn withdraw_stake_to_backup(ctx: Context<WithdrawStakeToBackup>) -> Result<()> {
+        instructions::process_withdraw_stake_to_backup(ctx)
+    }
+
+    pub fn add_yield_program(ctx: Context<AddYieldProgram>, yield_program: Pubkey) -> Result<()> {
+        instructions::process_add_yield_program(ctx, yield_program)
+    }
+
+    pub fn remove_yield_program(
+        ctx: Context<RemoveYieldProgram>,
+        yield_program: Pubkey,
+    ) -> Result<()> {
+        instructions::process_remove_yield_program(ctx, yield_program)
+    }
+
+    pub fn enter_yield_position(ctx: Context<EnterYieldPosition>, amount: u64) -> Result<()> {
+        instructions::process_enter_yield_position(ctx, amount)
+    }
+
+    pub fn exit_yield_position(ctx: Context<ExitYieldPosition>, shares: u64) -> Result<()> {
+        instructions::process_exit_yield_position(ctx, shares)
+    }
+
+    pub fn withdraw_yield_position_to_backup<'info>(
+        ctx: Context<'_, '_, '_, 'info, WithdrawYieldPositionToBackup<'info>>,
+    ) -> Result<()> {
+        instructions::process_withdraw_yield_position_to_backup(ctx)
+    }
+
+    pub fn quote_fee(
+        ctx: Context<QuoteFee>,
+        operation: custom_accounts::legate::FeeOperation,
+        mint: Pubkey,
+        amount: u64,
+    ) -> Result<u64> {
+        instructions::process_quote_fee(ctx, operation, mint, amount)
+    }
+
+    pub fn quote_usd_value(ctx: Context<QuoteUsdValue>, mint: Pubkey, amount: u64) -> Result<u64> {
+        instructions::process_quote_usd_value(ctx, mint, amount)
+    }
+
+    pub fn quote_sol_balance(ctx: Context<QuoteSolBalance>) -> Result<u64> {
+        instructions::process_quote_sol_balance(ctx)
+    }
+
+    pub fn quote_yield_position(ctx: Context<QuoteYieldPosition>) -> Result<u64> {
+        instructions::process_quote_yield_position(ctx)
+    }
+
+    pub fn swap<'info>(
+        ctx: Context<'_, '_, '_, 'info, Swap<'info>>,
+        router_swap: instructions::swaps::router::RouterInstructionWithIdxs,
+        router_setup: Vec<instructions::swaps::router::RouterInstructionWithIdxs>, // This involves SOL -> WSOL version and other accounts setup
+        router_cleanup: Option<instructions::swaps::router::RouterInstructionWithIdxs>, // Idxs to clean up accounts etc post swap
+        min_amount_out: u64,
+        max_amount_in: u64,
     ) -> Result<()> {
         instructions::process_swap(
             ctx,
-            jupiter_swap,
-            jupiter_setup,
-            jupiter_cleanup,
-            testudo_data,
+            router_swap,
+            router_setup,
+            router_cleanup,
+            min_amount_out,
+            max_amount_in,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_dca_order(
+        ctx: Context<CreateDcaOrder>,
+        order_id: u64,
+        amount_per_cycle: u64,
+        min_amount_out_per_cycle: u64,
+        interval_seconds: u64,
+        total_cycles: u16,
+        keeper_tip_lamports: u64,
+        max_slippage_bps: Option<u16>,
+    ) -> Result<()> {
+        instructions::process_create_dca_order(
+            ctx,
+            order_id,
+            amount_per_cycle,
+            min_amount_out_per_cycle,
+            interval_seconds,
+            total_cycles,
+            keeper_tip_lamports,
+            max_slippage_bps,
+        )
+    }
+
+    pub fn cancel_dca_order(ctx: Context<CancelDcaOrder>) -> Result<()> {
+        instructions::process_cancel_dca_order(ctx)
+    }
+
+    pub fn execute_dca_cycle<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteDcaCycle<'info>>,
+        router_swap: instructions::swaps::router::RouterInstructionWithIdxs,
+        router_setup: Vec<instructions::swaps::router::RouterInstructionWithIdxs>,
+        router_cleanup: Option<instructions::swaps::router::RouterInstructionWithIdxs>,
+        min_amount_out: u64,
+    ) -> Result<()> {
+        instructions::process_execute_dca_cycle(
+            ctx,
+            router_swap,
+            router_setup,
+            router_cleanup,
+            min_amount_out,
         )
     }
+
+    pub fn update_mint_price_feed(
+        ctx: Context<UpdateMintPriceFeed>,
+        mint: Pubkey,
+        new_price_feed: Option<custom_accounts::legate::PriceFeed>,
+    ) -> Result<()> {
+        instructions::process_update_mint_price_feed(ctx, mint, new_price_feed)
+    }
+
+    pub fn update_mint_transfer_hook(
+        ctx: Context<UpdateMintTransferHook>,
+        new_transfer_hook_program: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::process_update_mint_transfer_hook(ctx, new_transfer_hook_program)
+    }
+
+    pub fn update_oracle_settings(
+        ctx: Context<UpdateOracleSettings>,
+        new_max_price_staleness_seconds: u64,
+        new_max_price_confidence_bps: u16,
+    ) -> Result<()> {
+        instructions::process_update_oracle_settings(
+            ctx,
+            new_max_price_staleness_seconds,
+            new_max_price_confidence_bps,
+        )
+    }
+
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_conditional_order(
+        ctx: Context<CreateConditionalOrder>,
+        order_id: u64,
+        amount_in: u64,
+        min_amount_out: u64,
+        trigger: custom_accounts::conditional_order::OrderTrigger,
+        trigger_price: u64,
+        keeper_tip_lamports: u64,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        instructions::process_create_conditional_order(
+            ctx,
+            order_id,
+            amount_in,
+            min_amount_out,
+            trigger,
+            trigger_price,
+            keeper_tip_lamports,
+            max_slippage_bps,
+        )
+    }
+
+    pub fn cancel_conditional_order(ctx: Context<CancelConditionalOrder>) -> Result<()> {
+        instructions::process_cancel_conditional_order(ctx)
+    }
+
+    pub fn execute_conditional_order<'info>(
+        ctx: Context<'_, '_, '_, 'info, ExecuteConditionalOrder<'info>>,
+        router_swap: instructions::swaps::router::RouterInstructionWithIdxs,
+        router_setup: Vec<instructions::swaps::router::RouterInstructionWithIdxs>,
+        router_cleanup: Option<instructions::swaps::router::RouterInstructionWithIdxs>,
+    ) -> Result<()> {
+        instructions::process_execute_conditional_order(
+            ctx,
+            router_swap,
+            router_setup,
+            router_cleanup,
+        )
+    }
+
+    pub fn set_rebalance_policy(
+        ctx: Context<SetRebalancePolicy>,
+        targets: Vec<custom_accounts::rebalance_policy::RebalanceTarget>,
+        drift_threshold_bps: u16,
+        max_slippage_bps: u16,
+        min_interval_seconds: u64,
+    ) -> Result<()> {
+        instructions::process_set_rebalance_policy(
+            ctx,
+            targets,
+            drift_threshold_bps,
+            max_slippage_bps,
+            min_interval_seconds,
+        )
+    }
+
+    pub fn close_rebalance_policy(ctx: Context<CloseRebalancePolicy>) -> Result<()> {
+        instructions::process_close_rebalance_policy(ctx)
+    }
+
+    pub fn rebalance<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Rebalance<'info>>,
+        legs: Vec<RebalanceLeg>,
+    ) -> Result<()> {
+        instructions::process_rebalance(ctx, legs)
+    }
 }
@@ -0,0 +1,75 @@
+use crate::constants::TOKEN_METADATA_PROGRAM_ID;
+use crate::errors::ErrorCode::{InvalidNftMetadata, UnsupportedNftStandard};
+use anchor_lang::prelude::*;
+
+// Minimal reader for Metaplex Token Metadata accounts, decoding the fields up to the collection.
+// The account address is checked by the caller's seeds constraint.
+
+const METADATA_KEY_V1: u8 = 4;
+// Token standards whose token accounts move with plain token transfers. Programmable NFTs and their
+// editions (4 and 5), and any standard added later, are not accepted
+const TOKEN_STANDARD_NON_FUNGIBLE: u8 = 0;
+const TOKEN_STANDARD_FUNGIBLE_ASSET: u8 = 1;
+const TOKEN_STANDARD_FUNGIBLE: u8 = 2;
+const TOKEN_STANDARD_NON_FUNGIBLE_EDITION: u8 = 3;
+const SUPPORTED_TOKEN_STANDARDS: [u8; 4] = [
+    TOKEN_STANDARD_NON_FUNGIBLE,
+    TOKEN_STANDARD_FUNGIBLE_ASSET,
+    TOKEN_STANDARD_FUNGIBLE,
+    TOKEN_STANDARD_NON_FUNGIBLE_EDITION,
+];
+
+#[derive(AnchorDeserialize)]
+struct Creator {
+    _address: Pubkey,
+    _verified: bool,
+    _share: u8,
+}
+
+#[derive(AnchorDeserialize)]
+struct Collection {
+    verified: bool,
+    key: Pubkey,
+}
+
+#[derive(AnchorDeserialize)]
+struct Metadata {
+    key: u8,
+    _update_authority: Pubkey,
+    mint: Pubkey,
+    _name: String,
+    _symbol: String,
+    _uri: String,
+    _seller_fee_basis_points: u16,
+    _creators: Option<Vec<Creator>>,
+    _primary_sale_happened: bool,
+    _is_mutable: bool,
+    _edition_nonce: Option<u8>,
+    token_standard: Option<u8>,
+    collection: Option<Collection>,
+}
+
+// Collection the mint's metadata places it in, if the collection authority verified it.
+// Programmable NFTs are rejected: their token accounts stay frozen outside Token Metadata transfers.
+// Metadata without a token standard predates the field and is a plain NFT or fungible asset.
+pub fn verified_collection(metadata_info: &AccountInfo, mint: &Pubkey) -> Result<Option<Pubkey>> {
+    require_keys_eq!(
+        *metadata_info.owner,
+        TOKEN_METADATA_PROGRAM_ID,
+        InvalidNftMetadata
+    );
+    let data = metadata_info.try_borrow_data()?;
+    let metadata = Metadata::deserialize(&mut &data[..]).map_err(|_| error!(InvalidNftMetadata))?;
+    require_eq!(metadata.key, METADATA_KEY_V1, InvalidNftMetadata);
+    require_keys_eq!(metadata.mint, *mint, InvalidNftMetadata);
+    let supported_standard = match metadata.token_standard {
+        Some(standard) => SUPPORTED_TOKEN_STANDARDS.contains(&standard),
+        None => true,
+    };
+    require_eq!(supported_standard, true, UnsupportedNftStandard);
+
+    Ok(metadata
+        .collection
+        .filter(|collection| collection.verified)
+        .map(|collection| collection.key))
+}
@@ -0,0 +1,81 @@
+use crate::constants::{
+    MINT_RISK_FREEZE_AUTHORITY, MINT_RISK_TRANSFER_FEE, MINT_RISK_TRANSFER_HOOK,
+};
+use crate::errors::ErrorCode::{
+    MintDefaultFrozen, MintHasPermanentDelegate, MintNonTransferable, UnexpectedTransferHook,
+};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        default_account_state::DefaultAccountState, non_transferable::NonTransferable,
+        permanent_delegate::PermanentDelegate, transfer_fee::TransferFeeConfig,
+        transfer_hook::TransferHook, BaseStateWithExtensions, StateWithExtensions,
+    },
+    state::AccountState,
+};
+use anchor_spl::token_interface::Mint;
+
+// Issuer powers that break the vault promise are rejected outright: a permanent delegate can move testudo
+// funds, non-transferable tokens can never leave, default-frozen accounts can't receive deposits, and an
+// unapproved transfer hook runs arbitrary code on every transfer. Milder powers are returned as MINT_RISK_*
+// flags for the whitelist entry.
+pub fn inspect_mint_risk(
+    mint: &InterfaceAccount<Mint>,
+    transfer_hook_program: Option<Pubkey>,
+) -> Result<u8> {
+    let mut risk_flags = 0;
+    if mint.freeze_authority.is_some() {
+        risk_flags |= MINT_RISK_FREEZE_AUTHORITY;
+    }
+
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        require_eq!(
+            transfer_hook_program.is_none(),
+            true,
+            UnexpectedTransferHook
+        );
+        return Ok(risk_flags);
+    }
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+
+    if let Ok(permanent_delegate) = mint_state.get_extension::<PermanentDelegate>() {
+        require_eq!(
+            Option::<Pubkey>::from(permanent_delegate.delegate).is_none(),
+            true,
+            MintHasPermanentDelegate
+        );
+    }
+    require_eq!(
+        mint_state.get_extension::<NonTransferable>().is_err(),
+        true,
+        MintNonTransferable
+    );
+    if let Ok(default_state) = mint_state.get_extension::<DefaultAccountState>() {
+        require_eq!(
+            (default_state.state != AccountState::Frozen as u8),
+            true,
+            MintDefaultFrozen
+        );
+    }
+
+    // The hook program must match the one approved for the entry, including having none
+    let hook_program = mint_state
+        .get_extension::<TransferHook>()
+        .ok()
+        .and_then(|hook| Option::<Pubkey>::from(hook.program_id));
+    require_eq!(
+        (hook_program == transfer_hook_program),
+        true,
+        UnexpectedTransferHook
+    );
+    if hook_program.is_some() {
+        risk_flags |= MINT_RISK_TRANSFER_HOOK;
+    }
+    if mint_state.get_extension::<TransferFeeConfig>().is_ok() {
+        risk_flags |= MINT_RISK_TRANSFER_FEE;
+    }
+    Ok(risk_flags)
+}
@@ -0,0 +1,251 @@
+use crate::constants::{FEE_PERCENT_DENOMINATOR, PYTH_RECEIVER_PROGRAM_ID, USD_DECIMALS};
+use crate::custom_accounts::legate::{Legate, OracleKind, PriceFeed};
+use crate::errors::ErrorCode::{
+    ArithmeticOverflow, InvalidPriceAccount, InvalidPriceFeed, MissingPriceFeed,
+    PriceConfidenceTooWide, PriceUnavailable, StalePrice,
+};
+use anchor_lang::prelude::*;
+
+// Price reads from the oracle accounts registered on the Legate, and USD valuation built on them.
+// Only the fields the program needs are parsed, straight from the account data, so no oracle SDK is
+// required. Each OracleKind has its own parser; supporting a new format means adding a kind and a parser.
+
+const PYTH_MAGIC: u32 = 0xa1b2c3d4;
+const PYTH_VERSION: u32 = 2;
+const PYTH_ACCOUNT_TYPE_PRICE: u32 = 3;
+const PYTH_STATUS_TRADING: u32 = 1;
+
+// Byte offsets within a Pyth v2 price account
+const MAGIC_OFFSET: usize = 0;
+const VERSION_OFFSET: usize = 4;
+const ACCOUNT_TYPE_OFFSET: usize = 8;
+const EXPO_OFFSET: usize = 20;
+const TIMESTAMP_OFFSET: usize = 96;
+const AGG_PRICE_OFFSET: usize = 208;
+const AGG_CONF_OFFSET: usize = 216;
+const AGG_STATUS_OFFSET: usize = 224;
+const PRICE_ACCOUNT_MIN_LEN: usize = 240;
+
+// Pyth pull oracle PriceUpdateV2 account (Anchor account of the Pyth receiver program)
+const PRICE_UPDATE_DISCRIMINATOR: [u8; 8] = [34, 241, 35, 99, 157, 126, 244, 205];
+const VERIFICATION_LEVEL_OFFSET: usize = 40; // After the discriminator and write authority
+const VERIFICATION_LEVEL_FULL: u8 = 1;
+// Offsets within the price message, which follows the one-byte Full verification level
+const MESSAGE_FEED_ID_OFFSET: usize = 0;
+const MESSAGE_PRICE_OFFSET: usize = 32; // After the feed id
+const MESSAGE_CONF_OFFSET: usize = 40;
+const MESSAGE_EXPO_OFFSET: usize = 48;
+const MESSAGE_PUBLISH_TIME_OFFSET: usize = 52;
+const PRICE_MESSAGE_LEN: usize = 84;
+
+// Aggregate price of a feed: `price * 10^expo`, within +/- `conf * 10^expo`
+#[derive(Clone, Copy, Debug)]
+pub struct OraclePrice {
+    pub price: i64,
+    pub conf: u64,
+    pub expo: i32,
+    pub publish_time: i64,
+}
+
+impl OraclePrice {
+    // Price as an unsigned integer with `decimals` decimals (e.g. 6 for micro-USD). Rounds down
+    pub fn scaled_to(&self, decimals: u8) -> Result<u64> {
+        require_gt!(self.price, 0, PriceUnavailable);
+        let price = self.price as u128;
+        let shift = decimals as i32 + self.expo;
+        let scaled = if shift >= 0 {
+            price.checked_mul(10u128.checked_pow(shift as u32).ok_or(ArithmeticOverflow)?)
+        } else {
+            price.checked_div(
+                10u128
+                    .checked_pow((-shift) as u32)
+                    .ok_or(ArithmeticOverflow)?,
+            )
+        }
+        .ok_or(ArithmeticOverflow)?;
+        u64::try_from(scaled).map_err(|_| ArithmeticOverflow.into())
+    }
+}
+
+fn read_u32(data: &[u8], offset: usize) -> u32 {
+    u32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_i32(data: &[u8], offset: usize) -> i32 {
+    i32::from_le_bytes(data[offset..offset + 4].try_into().unwrap())
+}
+
+fn read_u64(data: &[u8], offset: usize) -> u64 {
+    u64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+fn read_i64(data: &[u8], offset: usize) -> i64 {
+    i64::from_le_bytes(data[offset..offset + 8].try_into().unwrap())
+}
+
+// Parse the aggregate price out of a Pyth v2 price account
+fn parse_pyth_v2(price_account: &AccountInfo) -> Result<OraclePrice> {
+    let data = price_account.try_borrow_data()?;
+    require_gte!(data.len(), PRICE_ACCOUNT_MIN_LEN, InvalidPriceAccount);
+    require_eq!(
+        read_u32(&data, MAGIC_OFFSET),
+        PYTH_MAGIC,
+        InvalidPriceAccount
+    );
+    require_eq!(
+        read_u32(&data, VERSION_OFFSET),
+        PYTH_VERSION,
+        InvalidPriceAccount
+    );
+    require_eq!(
+        read_u32(&data, ACCOUNT_TYPE_OFFSET),
+        PYTH_ACCOUNT_TYPE_PRICE,
+        InvalidPriceAccount
+    );
+    require_eq!(
+        read_u32(&data, AGG_STATUS_OFFSET),
+        PYTH_STATUS_TRADING,
+        PriceUnavailable
+    );
+
+    Ok(OraclePrice {
+        price: read_i64(&data, AGG_PRICE_OFFSET),
+        conf: read_u64(&data, AGG_CONF_OFFSET),
+        expo: read_i32(&data, EXPO_OFFSET),
+        publish_time: read_i64(&data, TIMESTAMP_OFFSET),
+    })
+}
+
+// Parse the price message out of a fully verified Pyth PriceUpdateV2 account. Anyone can create an
+// account with this layout, so it must be owned by the Pyth receiver and carry the expected feed
+fn parse_pyth_price_update(price_account: &AccountInfo, feed_id: &[u8; 32]) -> Result<OraclePrice> {
+    require_keys_eq!(
+        *price_account.owner,
+        PYTH_RECEIVER_PROGRAM_ID,
+        InvalidPriceAccount
+    );
+    let data = price_account.try_borrow_data()?;
+    let message_offset = VERIFICATION_LEVEL_OFFSET + 1;
+    require_gte!(
+        data.len(),
+        message_offset + PRICE_MESSAGE_LEN,
+        InvalidPriceAccount
+    );
+    require_eq!(
+        (data[..8] == PRICE_UPDATE_DISCRIMINATOR),
+        true,
+        InvalidPriceAccount
+    );
+    // Partially verified updates carry fewer guardian signatures than Pyth requires
+    require_eq!(
+        data[VERIFICATION_LEVEL_OFFSET],
+        VERIFICATION_LEVEL_FULL,
+        PriceUnavailable
+    );
+    let feed_id_offset = message_offset + MESSAGE_FEED_ID_OFFSET;
+    require_eq!(
+        (data[feed_id_offset..feed_id_offset + 32] == feed_id[..]),
+        true,
+        InvalidPriceFeed
+    );
+
+    Ok(OraclePrice {
+        price: read_i64(&data, message_offset + MESSAGE_PRICE_OFFSET),
+        conf: read_u64(&data, message_offset + MESSAGE_CONF_OFFSET),
+        expo: read_i32(&data, message_offset + MESSAGE_EXPO_OFFSET),
+        publish_time: read_i64(&data, message_offset + MESSAGE_PUBLISH_TIME_OFFSET),
+    })
+}
+
+// Parse a price with the parser of the feed's kind. The account must be the one registered for the feed
+pub fn load_price(feed: &PriceFeed, price_account: &AccountInfo) -> Result<OraclePrice> {
+    require_keys_eq!(price_account.key(), feed.price_account, InvalidPriceFeed);
+    match feed.kind {
+        OracleKind::PythV2 => parse_pyth_v2(price_account),
+        OracleKind::PythPriceUpdate { feed_id } => parse_pyth_price_update(price_account, &feed_id),
+    }
+}
+
+// Load a price and reject it if it is older, or less certain, than the Legate allows
+pub fn load_checked_price(
+    legate: &Legate,
+    feed: &PriceFeed,
+    price_account: &AccountInfo,
+) -> Result<OraclePrice> {
+    let price = load_price(feed, price_account)?;
+    require_gt!(price.price, 0, PriceUnavailable);
+
+    let now = Clock::get()?.unix_timestamp;
+    let age = now.saturating_sub(price.publish_time).max(0) as u64;
+    require_gte!(legate.max_price_staleness_seconds, age, StalePrice);
+
+    // conf / price <= max_price_confidence_bps / 10000
+    let conf_bps = (price.conf as u128) * 10000 / (price.price as u128);
+    require_gte!(
+        legate.max_price_confidence_bps as u128,
+        conf_bps,
+        PriceConfidenceTooWide
+    );
+
+    Ok(price)
+}
+
+// Checked price of a whitelisted mint in micro-USD per whole token
+pub fn mint_price_usd(legate: &Legate, mint: &Pubkey, price_account: &AccountInfo) -> Result<u64> {
+    let feed = legate.price_feed_for_mint(mint).ok_or(MissingPriceFeed)?;
+    load_checked_price(legate, &feed, price_account)?.scaled_to(USD_DECIMALS)
+}
+
+// USD value, in micro-USD, of `amount` base units at `price` micro-USD per whole token. Rounds down
+pub fn usd_value(amount: u64, decimals: u8, price: u64) -> Result<u64> {
+    let value = (amount as u128)
+        .checked_mul(price as u128)
+        .and_then(|value| value.checked_div(10u128.checked_pow(decimals as u32)?))
+        .ok_or(ArithmeticOverflow)?;
+    u64::try_from(value).map_err(|_| ArithmeticOverflow.into())
+}
+
+// USD value, in micro-USD, of an amount of a whitelisted mint at its checked oracle price
+pub fn value_in_usd(
+    legate: &Legate,
+    mint: &Pubkey,
+    amount: u64,
+    decimals: u8,
+    price_account: &AccountInfo,
+) -> Result<u64> {
+    usd_value(
+        amount,
+        decimals,
+        mint_price_usd(legate, mint, price_account)?,
+    )
+}
+
+// Least amount of the destination mint that `amount_in` of the source mint should buy at their oracle
+// prices (micro-USD per whole token), less a slippage allowance in basis points. Rounds down
+pub fn oracle_min_amount_out(
+    amount_in: u64,
+    source_decimals: u8,
+    source_price: u64,
+    destination_decimals: u8,
+    destination_price: u64,
+    max_slippage_bps: u16,
+) -> Result<u64> {
+    require_gt!(destination_price, 0, PriceUnavailable);
+    let value_in = (amount_in as u128)
+        .checked_mul(source_price as u128)
+        .and_then(|value| value.checked_div(10u128.checked_pow(source_decimals as u32)?))
+        .ok_or(ArithmeticOverflow)?;
+    let min_value_out = value_in
+        .checked_mul(FEE_PERCENT_DENOMINATOR.saturating_sub(max_slippage_bps) as u128)
+        .ok_or(ArithmeticOverflow)?
+        / FEE_PERCENT_DENOMINATOR as u128;
+    let min_amount_out = min_value_out
+        .checked_mul(
+            10u128
+                .checked_pow(destination_decimals as u32)
+                .ok_or(ArithmeticOverflow)?,
+        )
+        .ok_or(ArithmeticOverflow)?
+        / destination_price as u128;
+    u64::try_from(min_amount_out).map_err(|_| ArithmeticOverflow.into())
+}
@@ -0,0 +1,191 @@
+use crate::custom_accounts::legate::Legate;
+use crate::errors::ErrorCode::{ArithmeticOverflow, UnexpectedTransferHook};
+use anchor_lang::prelude::*;
+use anchor_spl::token_2022::spl_token_2022::{
+    self,
+    extension::{
+        transfer_fee::{TransferFeeAmount, TransferFeeConfig},
+        transfer_hook, BaseStateWithExtensions, StateWithExtensions,
+    },
+    onchain::{invoke_transfer_checked, invoke_transfer_checked_with_fee},
+};
+use anchor_spl::token_interface::{
+    harvest_withheld_tokens_to_mint, transfer_checked, transfer_checked_with_fee,
+    HarvestWithheldTokensToMint, Mint, TransferChecked, TransferCheckedWithFee,
+};
+
+// Shared token transfers for every path that moves testudo tokens. Token-2022 mints with the TransferFee
+// extension withhold part of each transfer in the destination account, so the amount received is less
+// than the amount sent; these helpers state the expected fee and report what actually arrives. Mints with the
+// TransferHook extension need the hook's extra accounts, which callers pass through `remaining_accounts`.
+
+// Fee withheld by the mint's TransferFee extension on a transfer of `amount` this epoch.
+// None when the mint has no TransferFee extension.
+pub fn mint_transfer_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<Option<u64>> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    match mint_state.get_extension::<TransferFeeConfig>() {
+        Ok(config) => Ok(Some(
+            config
+                .calculate_epoch_fee(Clock::get()?.epoch, amount)
+                .ok_or(ArithmeticOverflow)?,
+        )),
+        Err(_) => Ok(None),
+    }
+}
+
+// Amount that arrives when `amount` is sent, after the mint's transfer fee
+pub fn amount_after_transfer_fee(mint: &InterfaceAccount<Mint>, amount: u64) -> Result<u64> {
+    let transfer_fee = mint_transfer_fee(mint, amount)?.unwrap_or(0);
+    amount
+        .checked_sub(transfer_fee)
+        .ok_or(ArithmeticOverflow.into())
+}
+
+// Program the mint's TransferHook extension invokes on every transfer, if any
+pub fn mint_transfer_hook_program(mint: &InterfaceAccount<Mint>) -> Result<Option<Pubkey>> {
+    let mint_info = mint.to_account_info();
+    if *mint_info.owner != spl_token_2022::ID {
+        return Ok(None);
+    }
+    let data = mint_info.try_borrow_data()?;
+    let mint_state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    Ok(transfer_hook::get_program_id(&mint_state))
+}
+
+// The hook authority can repoint a mint's hook after whitelisting, so transfers re-check it against the
+// program approved on the whitelist entry
+pub fn require_approved_transfer_hook(
+    legate: &Legate,
+    mint: &InterfaceAccount<Mint>,
+) -> Result<()> {
+    require_eq!(
+        (mint_transfer_hook_program(mint)? == legate.transfer_hook_program_for_mint(&mint.key())),
+        true,
+        UnexpectedTransferHook
+    );
+    Ok(())
+}
+
+// Transfer `amount` of `mint`, with the expected transfer fee when the mint charges one.
+// `hook_accounts` must hold the hook program, its validation account and extra metas for hooked mints.
+// Returns the amount received by `to`.
+#[allow(clippy::too_many_arguments)]
+pub fn transfer_tokens<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+    hook_accounts: &[AccountInfo<'info>],
+) -> Result<u64> {
+    let transfer_fee = mint_transfer_fee(mint, amount)?;
+    if mint_transfer_hook_program(mint)?.is_some() {
+        // Token-2022 resolves the extra account metas from the hook's validation account
+        match transfer_fee {
+            Some(transfer_fee) => invoke_transfer_checked_with_fee(
+                token_program.key,
+                from,
+                mint.to_account_info(),
+                to,
+                authority,
+                hook_accounts,
+                amount,
+                mint.decimals,
+                transfer_fee,
+                signer_seeds,
+            )?,
+            None => invoke_transfer_checked(
+                token_program.key,
+                from,
+                mint.to_account_info(),
+                to,
+                authority,
+                hook_accounts,
+                amount,
+                mint.decimals,
+                signer_seeds,
+            )?,
+        }
+        return amount
+            .checked_sub(transfer_fee.unwrap_or(0))
+            .ok_or(ArithmeticOverflow.into());
+    }
+
+    match transfer_fee {
+        Some(transfer_fee) => {
+            let cpi_accounts = TransferCheckedWithFee {
+                token_program_id: token_program.clone(),
+                source: from,
+                mint: mint.to_account_info(),
+                destination: to,
+                authority,
+            };
+            transfer_checked_with_fee(
+                CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds),
+                amount,
+                mint.decimals,
+                transfer_fee,
+            )?;
+            amount
+                .checked_sub(transfer_fee)
+                .ok_or(ArithmeticOverflow.into())
+        }
+        None => {
+            let cpi_accounts = TransferChecked {
+                from,
+                mint: mint.to_account_info(),
+                to,
+                authority,
+            };
+            transfer_checked(
+                CpiContext::new_with_signer(token_program, cpi_accounts, signer_seeds),
+                amount,
+                mint.decimals,
+            )?;
+            Ok(amount)
+        }
+    }
+}
+
+// Move transfer fees withheld in a token account to its mint, so the account can be closed.
+// Harvesting is permissionless; nothing is done for accounts without withheld fees.
+pub fn harvest_withheld_fees<'info>(
+    token_program: AccountInfo<'info>,
+    mint: &InterfaceAccount<'info, Mint>,
+    token_account: AccountInfo<'info>,
+) -> Result<()> {
+    if *token_account.owner != spl_token_2022::ID {
+        return Ok(());
+    }
+    let withheld_amount = {
+        let data = token_account.try_borrow_data()?;
+        let account_state = StateWithExtensions::<spl_token_2022::state::Account>::unpack(&data)?;
+        match account_state.get_extension::<TransferFeeAmount>() {
+            Ok(fee_amount) => u64::from(fee_amount.withheld_amount),
+            Err(_) => 0,
+        }
+    };
+    if withheld_amount == 0 {
+        return Ok(());
+    }
+
+    msg!(
+        "Harvesting {} withheld transfer fee tokens",
+        withheld_amount
+    );
+    let cpi_accounts = HarvestWithheldTokensToMint {
+        token_program_id: token_program.clone(),
+        mint: mint.to_account_info(),
+    };
+    harvest_withheld_tokens_to_mint(
+        CpiContext::new(token_program, cpi_accounts),
+        vec![token_account],
+    )
+}
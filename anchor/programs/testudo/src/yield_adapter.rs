@@ -0,0 +1,174 @@
+use crate::errors::ErrorCode::{MissingYieldReturnData, YieldAdapterMisbehaved};
+use crate::instructions::swaps::guards::{
+    snapshot_centurion_token_balances, verify_no_centurion_token_losses,
+};
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::{
+    instruction::{AccountMeta, Instruction},
+    program::{get_return_data, invoke_signed},
+};
+use anchor_spl::token_interface::TokenAccount;
+
+// Yield adapter interface. A Legate-approved yield program implements three instructions, identified by
+// sha256("yield_adapter:<name>")[..8], and is driven over CPI with the Centurion as owner:
+//
+//   deposit(amount: u64)  - take `amount` of the underlying from owner_underlying, mint position tokens
+//                           to owner_position
+//   withdraw(shares: u64) - burn `shares` position tokens from owner_position, pay the underlying they are
+//                           worth to owner_underlying
+//   accounts: [vault (w), underlying_mint, position_mint (w), vault_reserve (w), owner_underlying (w),
+//              owner_position (w), owner (signer), token_program]
+//
+//   position_value(shares: u64) - set the underlying amount `shares` redeem for as u64 return data
+//   accounts: [vault, vault_reserve, position_mint]
+
+pub const YIELD_DEPOSIT_DISCRIMINATOR: [u8; 8] = [163, 150, 39, 75, 39, 90, 123, 189];
+pub const YIELD_WITHDRAW_DISCRIMINATOR: [u8; 8] = [171, 156, 210, 254, 15, 187, 94, 86];
+pub const YIELD_POSITION_VALUE_DISCRIMINATOR: [u8; 8] = [120, 246, 202, 237, 237, 51, 120, 18];
+
+// Accounts for an adapter deposit or withdraw, in interface order
+pub struct YieldAdapterAccounts<'info> {
+    pub adapter_program: AccountInfo<'info>,
+    pub vault: AccountInfo<'info>,
+    pub underlying_mint: AccountInfo<'info>,
+    pub position_mint: AccountInfo<'info>,
+    pub vault_reserve: AccountInfo<'info>,
+    pub owner_underlying: AccountInfo<'info>,
+    pub owner_position: AccountInfo<'info>,
+    pub owner: AccountInfo<'info>,
+    pub token_program: AccountInfo<'info>,
+}
+
+fn instruction_data(discriminator: [u8; 8], amount: u64) -> Vec<u8> {
+    let mut data = discriminator.to_vec();
+    data.extend_from_slice(&amount.to_le_bytes());
+    data
+}
+
+// Run the adapter's deposit or withdraw with the Centurion signing as owner. Only the interface accounts are
+// passed, and any other Centurion-owned token account among them must come out no poorer.
+pub fn invoke_yield_adapter<'info>(
+    accounts: YieldAdapterAccounts<'info>,
+    discriminator: [u8; 8],
+    amount: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    let metas = vec![
+        AccountMeta::new(accounts.vault.key(), false),
+        AccountMeta::new_readonly(accounts.underlying_mint.key(), false),
+        AccountMeta::new(accounts.position_mint.key(), false),
+        AccountMeta::new(accounts.vault_reserve.key(), false),
+        AccountMeta::new(accounts.owner_underlying.key(), false),
+        AccountMeta::new(accounts.owner_position.key(), false),
+        AccountMeta::new_readonly(accounts.owner.key(), true),
+        AccountMeta::new_readonly(accounts.token_program.key(), false),
+    ];
+    // The vault accounts are the adapter's to move; a Centurion token account passed in their place is not
+    let centurion = accounts.owner.key();
+    let excluded = [
+        accounts.owner_underlying.key(),
+        accounts.owner_position.key(),
+    ];
+
+    let infos = vec![
+        accounts.vault,
+        accounts.underlying_mint,
+        accounts.position_mint,
+        accounts.vault_reserve,
+        accounts.owner_underlying,
+        accounts.owner_position,
+        accounts.owner,
+        accounts.token_program,
+        accounts.adapter_program.clone(),
+    ];
+    let guarded_balances_before = snapshot_centurion_token_balances(&infos, &centurion, &excluded);
+
+    invoke_signed(
+        &Instruction {
+            program_id: accounts.adapter_program.key(),
+            accounts: metas,
+            data: instruction_data(discriminator, amount),
+        },
+        &infos,
+        signer_seeds,
+    )?;
+
+    verify_no_centurion_token_losses(&infos, &centurion, &guarded_balances_before)
+}
+
+// Redeem `shares` through the adapter's withdraw. The adapter must burn exactly `shares` and pay the underlying
+// into the testudo, leaving both accounts fully under the Centurion. Returns the underlying received
+pub fn redeem_position_shares<'info>(
+    accounts: YieldAdapterAccounts<'info>,
+    testudo: &mut InterfaceAccount<'info, TokenAccount>,
+    position_account: &mut InterfaceAccount<'info, TokenAccount>,
+    shares: u64,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<u64> {
+    let centurion = accounts.owner.key();
+    let underlying_before = testudo.amount;
+    let shares_before = position_account.amount;
+
+    invoke_yield_adapter(accounts, YIELD_WITHDRAW_DISCRIMINATOR, shares, signer_seeds)?;
+
+    testudo.reload()?;
+    position_account.reload()?;
+    let burned = shares_before
+        .checked_sub(position_account.amount)
+        .ok_or(YieldAdapterMisbehaved)?;
+    require_eq!(burned, shares, YieldAdapterMisbehaved);
+    let amount_received = testudo
+        .amount
+        .checked_sub(underlying_before)
+        .ok_or(YieldAdapterMisbehaved)?;
+    require_untouched_authorities(testudo, &centurion)?;
+    require_untouched_authorities(position_account, &centurion)?;
+    Ok(amount_received)
+}
+
+// Ask the adapter what `shares` of the position are worth in the underlying
+pub fn query_position_value<'info>(
+    adapter_program: AccountInfo<'info>,
+    vault: AccountInfo<'info>,
+    vault_reserve: AccountInfo<'info>,
+    position_mint: AccountInfo<'info>,
+    shares: u64,
+) -> Result<u64> {
+    let program_id = adapter_program.key();
+    invoke_signed(
+        &Instruction {
+            program_id,
+            accounts: vec![
+                AccountMeta::new_readonly(vault.key(), false),
+                AccountMeta::new_readonly(vault_reserve.key(), false),
+                AccountMeta::new_readonly(position_mint.key(), false),
+            ],
+            data: instruction_data(YIELD_POSITION_VALUE_DISCRIMINATOR, shares),
+        },
+        &[vault, vault_reserve, position_mint, adapter_program],
+        &[],
+    )?;
+
+    // The return data must come from the adapter itself, not a program it called
+    let (returned_by, data) = get_return_data().ok_or(MissingYieldReturnData)?;
+    require_keys_eq!(returned_by, program_id, MissingYieldReturnData);
+    let value: [u8; 8] = data
+        .get(..8)
+        .and_then(|bytes| bytes.try_into().ok())
+        .ok_or(MissingYieldReturnData)?;
+    Ok(u64::from_le_bytes(value))
+}
+
+// The Centurion signs the adapter CPI, so make sure no delegate or close authority was slipped in
+pub fn require_untouched_authorities(
+    token_account: &InterfaceAccount<TokenAccount>,
+    centurion: &Pubkey,
+) -> Result<()> {
+    require_keys_eq!(token_account.owner, *centurion, YieldAdapterMisbehaved);
+    require_eq!(
+        token_account.delegate.is_none() && token_account.close_authority.is_none(),
+        true,
+        YieldAdapterMisbehaved
+    );
+    Ok(())
+}
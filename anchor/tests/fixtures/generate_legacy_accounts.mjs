@@ -0,0 +1,147 @@
+// Writes the accounts the migration tests load into the test validator (see [[test.validator.account]] in
+// Anchor.toml). Each one is laid out the way the first deployed program wrote it, before the layout was
+// versioned. The keypairs are derived from fixed seeds, so rerunning only changes the files when the
+// localnet program id changes:
+//
+//   node tests/fixtures/generate_legacy_accounts.mjs
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { createHash } from "crypto";
+import { readFileSync, writeFileSync } from "fs";
+import { dirname, join } from "path";
+import { fileURLToPath } from "url";
+
+const fixturesDir = dirname(fileURLToPath(import.meta.url));
+const anchorToml = readFileSync(join(fixturesDir, "../../Anchor.toml"), "utf8");
+const programId = new PublicKey(
+	anchorToml.match(/\[programs\.localnet\][^[]*?testudo = "(\w+)"/)[1]
+);
+
+// Borsh encoding, as the program writes it
+const u8 = (value) => Buffer.from([value]);
+const bool = (value) => u8(value ? 1 : 0);
+const u16 = (value) => {
+	const buffer = Buffer.alloc(2);
+	buffer.writeUInt16LE(value);
+	return buffer;
+};
+const u32 = (value) => {
+	const buffer = Buffer.alloc(4);
+	buffer.writeUInt32LE(value);
+	return buffer;
+};
+const u64 = (value) => {
+	const buffer = Buffer.alloc(8);
+	buffer.writeBigUInt64LE(BigInt(value));
+	return buffer;
+};
+const pubkey = (key) => key.toBuffer();
+const string = (value) => Buffer.concat([u32(Buffer.byteLength(value)), Buffer.from(value)]);
+const vec = (items) => Buffer.concat([u32(items.length), ...items]);
+const discriminator = (name) =>
+	createHash("sha256").update(`account:${name}`).digest().subarray(0, 8);
+
+const keypairFromLabel = (label) =>
+	Keypair.fromSeed(createHash("sha256").update(label).digest());
+
+// Same rent the test validator charges: 3480 lamports per byte-year, exempt at two years
+const rentExemptLamports = (space) => (space + 128) * 3480 * 2;
+
+const writeKeypair = (name, keypair) =>
+	writeFileSync(
+		join(fixturesDir, `${name}.json`),
+		JSON.stringify(Array.from(keypair.secretKey))
+	);
+
+// Pads the account to the size the legacy program allocated, as `solana account --output json` prints it.
+// `heldLamports` is SOL the account holds on top of its rent
+const writeAccount = (name, address, fields, space, heldLamports = 0) => {
+	const data = Buffer.alloc(space);
+	fields.copy(data);
+	writeFileSync(
+		join(fixturesDir, `${name}.json`),
+		JSON.stringify(
+			{
+				pubkey: address.toBase58(),
+				account: {
+					lamports: rentExemptLamports(space) + heldLamports,
+					data: [data.toString("base64"), "base64"],
+					owner: programId.toBase58(),
+					executable: false,
+					rentEpoch: 0,
+					space,
+				},
+			},
+			null,
+			2
+		) + "\n"
+	);
+	console.log(`${name}: ${address.toBase58()}`);
+};
+
+// LEGATE. 8 + 4135 bytes: 50 whitelist entries of 81 bytes (mint, name, symbol, decimals)
+const legateAuthority = keypairFromLabel("testudo legacy legate authority");
+const legateTreasury = keypairFromLabel("testudo legacy legate treasury");
+const [legatePDA, legateBump] = PublicKey.findProgramAddressSync(
+	[Buffer.from("legate")],
+	programId
+);
+const legacyWhitelistEntry = (mint, name, symbol, decimals) =>
+	Buffer.concat([pubkey(new PublicKey(mint)), string(name), string(symbol), u8(decimals)]);
+
+writeKeypair("legacy-legate-authority", legateAuthority);
+writeAccount(
+	"legacy-legate",
+	legatePDA,
+	Buffer.concat([
+		discriminator("Legate"),
+		pubkey(legateAuthority.publicKey), // authority
+		u8(legateBump), // bump
+		bool(true), // is_initialized
+		u64(1_735_689_600), // last_updated
+		u8(1), // max_centurions_per_user
+		u16(30), // max_testudos_per_user
+		u16(50), // max_whitelisted_mints
+		pubkey(legateTreasury.publicKey), // treasury_acc
+		u16(15), // percent_for_fees
+		vec([
+			legacyWhitelistEntry("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "USD Coin", "USDC", 6),
+			legacyWhitelistEntry("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263", "Bonk", "BONK", 5),
+		]), // testudo_token_whitelist
+	]),
+	8 + 32 + 1 + 1 + 8 + 1 + 2 + 2 + 32 + 2 + 4 + 50 * 81
+);
+
+// CENTURION. 8 + 2047 bytes: 30 testudo entries of 64 bytes (mint, testudo account). The BONK testudo was
+// closed, so close_testudo left its entry's bytes behind the one remaining in the list
+const centurionAuthority = keypairFromLabel("testudo legacy centurion authority");
+const centurionPassword = keypairFromLabel("testudo legacy centurion password");
+const [centurionPDA, centurionBump] = PublicKey.findProgramAddressSync(
+	[Buffer.from("centurion"), centurionAuthority.publicKey.toBuffer()],
+	programId
+);
+const testudoEntry = (mint, label) =>
+	Buffer.concat([
+		pubkey(new PublicKey(mint)),
+		pubkey(keypairFromLabel(`testudo legacy centurion ${label} testudo`).publicKey),
+	]);
+
+writeKeypair("legacy-centurion-authority", centurionAuthority);
+writeAccount(
+	"legacy-centurion",
+	centurionPDA,
+	Buffer.concat([
+		discriminator("Centurion"),
+		pubkey(centurionAuthority.publicKey), // authority
+		u8(0), // backup_owner (None)
+		pubkey(centurionPassword.publicKey), // pubkey_to_password
+		u8(centurionBump), // bump
+		bool(true), // is_initialized
+		u64(1_735_689_600), // created_at
+		u64(1_738_368_000), // last_accessed
+		u64(250_000_000), // lamport_balance
+		vec([testudoEntry("EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v", "usdc")]), // testudos
+		testudoEntry("DezXAZ8z7PnrnRJjz3wXBoRgixCa6xjnB7YaB1pPB263", "bonk"), // stale, closed testudo
+	]),
+	8 + 32 + 33 + 32 + 1 + 1 + 8 + 8 + 8 + 4 + 30 * 64,
+	250_000_000 // deposited SOL, matching lamport_balance
+);
@@ -16,6 +16,8 @@ import { PublicKey, Keypair } from "@solana/web3.js";
 import * as anchor from "@coral-xyz/anchor";
 import { toast } from "react-hot-toast";
 
+const WSOL_MINT = new PublicKey("So11111111111111111111111111111111111111112");
+
 // Hook for using the withdraw modal
 export function useWithdrawModal() {
 	const [withdrawingTestudo, setWithdrawingTestudo] = useState<TestudoData | "SOL" | null>(null);
@@ -110,7 +112,6 @@ export function WithdrawModal({
 	const [error, setError] = useState<string | null>(null);
 	const [solBalance, setSolBalance] = useState<number>(0);
 	const [tokenBalance, setTokenBalance] = useState<number>(0);
-	const [feePercentage, setFeePercentage] = useState<number>(0);
 	const [feeAmount, setFeeAmount] = useState<string>("0");
 
 	// Get token info from the Legate account
@@ -146,44 +147,42 @@ export function WithdrawModal({
 		}
 	};
 
-	// Function to fetch the fee percentage from Legate account
-	const fetchFeePercentage = async () => {
-		try {
-			const [legatePDA] = findLegatePDA(testudoProgram.programId);
-			const legateAccount = await testudoProgram.account.legate.fetch(legatePDA);
-			
-			// percentForFees is stored as a u16, so divide by 100 to get percentage
-			// e.g., 250 => 2.5%
-			const percentage = (legateAccount.percentForFees / 100);
-			setFeePercentage(percentage);
-		} catch (error) {
-			console.error("Error fetching fee percentage:", error);
-			setFeePercentage(0);
-		}
-	};
-	
-	// Calculate fee amount when amount or fee percentage changes
+	// Quote the withdrawal fee through the program's quote_fee view, so mint overrides, fee tiers and
+	// minimum/flat fees match what the withdrawal will actually charge
 	useEffect(() => {
-		const calculateFee = () => {
-			if (!amount || isNaN(parseFloat(amount)) || feePercentage <= 0) {
+		if (!isOpen || !testudo) return;
+		let cancelled = false;
+
+		const quoteFee = async () => {
+			if (!amount || isNaN(parseFloat(amount)) || parseFloat(amount) <= 0) {
 				setFeeAmount("0");
 				return;
 			}
-			
-			const withdrawAmount = parseFloat(amount);
-			const calculatedFeeAmount = (withdrawAmount * feePercentage / 100).toFixed(tokenDecimals > 6 ? 6 : tokenDecimals);
-			setFeeAmount(calculatedFeeAmount);
+
+			try {
+				// SOL is quoted through the wSOL mint
+				const feeMint = testudo === "SOL" ? WSOL_MINT : new PublicKey(testudo.tokenMint);
+				const amountWithDecimals = Math.floor(parseFloat(amount) * Math.pow(10, tokenDecimals));
+				const quotedFee = await testudoProgram.methods
+					.quoteFee({ withdraw: {} }, feeMint, new anchor.BN(amountWithDecimals.toString()))
+					.view();
+				if (!cancelled) {
+					const fee = Number(quotedFee.toString()) / Math.pow(10, tokenDecimals);
+					setFeeAmount(fee.toFixed(tokenDecimals > 6 ? 6 : tokenDecimals));
+				}
+			} catch (error) {
+				console.error("Error quoting withdrawal fee:", error);
+				if (!cancelled) {
+					setFeeAmount("0");
+				}
+			}
 		};
-		
-		calculateFee();
-	}, [amount, feePercentage, tokenDecimals]);
 
-	// Fetch fee percentage when modal opens
-	useEffect(() => {
-		if (isOpen) {
-			fetchFeePercentage();
-		}
-	}, [isOpen, testudoProgram]);
+		quoteFee();
+		return () => {
+			cancelled = true;
+		};
+	}, [isOpen, amount, testudo, tokenDecimals, testudoProgram]);
 
 	// Function to handle actual withdrawal
 	const handleWithdraw = async (withdrawAmount: number, passwordKeypair: Keypair) => {
@@ -231,7 +230,7 @@ export function WithdrawModal({
 				onSuccess(updatedCenturionAccount);
 				
 				// Display success message with fee information
-				const feeAmountSOL = (withdrawAmount * feePercentage / 100);
+				const feeAmountSOL = parseFloat(feeAmount);
 				const receivedAmount = withdrawAmount - feeAmountSOL;
 				toast.success(
 					`Successfully withdrew ${withdrawAmount} SOL (Fee: ${feeAmountSOL.toFixed(6)} SOL, Received: ${receivedAmount.toFixed(6)} SOL)`
@@ -278,7 +277,7 @@ export function WithdrawModal({
 				onSuccess(updatedCenturionAccount);
 				
 				// Display success message with fee information
-				const feeAmountToken = (withdrawAmount * feePercentage / 100);
+				const feeAmountToken = parseFloat(feeAmount);
 				const receivedAmount = withdrawAmount - feeAmountToken;
 				toast.success(
 					`Successfully withdrew ${withdrawAmount} ${tokenInfo.symbol} (Fee: ${feeAmountToken.toFixed(tokenInfo.decimals > 6 ? 6 : tokenInfo.decimals)} ${tokenInfo.symbol}, Received: ${receivedAmount.toFixed(tokenInfo.decimals > 6 ? 6 : tokenInfo.decimals)} ${tokenInfo.symbol})`
@@ -462,10 +461,10 @@ export function WithdrawModal({
 							</p>
 							
 							{/* Fee information */}
-							{feePercentage > 0 && amount && !isNaN(parseFloat(amount)) && (
+							{parseFloat(feeAmount) > 0 && amount && !isNaN(parseFloat(amount)) && (
 								<div className="mt-2 p-2 bg-gray-800/80 rounded-md border border-gray-700">
 									<p className="text-xs text-amber-300">
-										Withdrawal Fee: {feeAmount} {tokenSymbol} ({feePercentage}%)
+										Withdrawal Fee: {feeAmount} {tokenSymbol}
 									</p>
 									<p className="text-xs text-gray-400 mt-0.5">
 										You will receive: {(parseFloat(amount) - parseFloat(feeAmount)).toFixed(tokenDecimals > 6 ? 6 : tokenDecimals)} {tokenSymbol}
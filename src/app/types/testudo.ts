@@ -14,11 +14,29 @@ export interface TestudoData {
 	testudoPubkey: PublicKey;
 }
 
+export interface PriceFeedData {
+	kind: { pythV2: {} } | { pythPriceUpdate: { feedId: number[] } };
+	priceAccount: PublicKey;
+}
+
 export interface TokenWhitelistData {
 	tokenMint: PublicKey;
 	tokenName: string;
 	tokenSymbol: string;
 	tokenDecimals: number;
+	percentForFees: number | null;
+	minFee: number;
+	flatFee: number;
+	priceFeed: PriceFeedData | null;
+	transferHookProgram: PublicKey | null;
+	riskFlags: number; // MINT_RISK_* flags set by the program when whitelisted
+}
+
+export interface YieldPositionData {
+	adapterProgram: PublicKey;
+	vault: PublicKey;
+	underlyingMint: PublicKey;
+	positionMint: PublicKey;
 }
 
 export interface CenturionData {
@@ -31,6 +49,29 @@ export interface CenturionData {
 	lastAccessed: number;
 	lamportBalance: number;
 	testudos: TestudoData[];
+	version: number; // Layout version, bumped by migrate_centurion
+	nfts: PublicKey[]; // Vaulted NFT and semi-fungible mints
+	stakeAccounts: PublicKey[];
+	yieldPositions: YieldPositionData[];
+	acceptsThirdPartyDeposits: boolean;
+}
+
+export interface FeeTierData {
+	threshold: number;
+	percentForFees: number;
+}
+
+export interface OperationFeeData {
+	enabled: boolean;
+	percentForFees: number | null;
+}
+
+export interface OperationFeesData {
+	withdraw: OperationFeeData;
+	backupRecovery: OperationFeeData;
+	close: OperationFeeData;
+	swap: OperationFeeData;
+	internalTransfer: OperationFeeData;
 }
 
 export interface LegateData {
@@ -41,9 +82,73 @@ export interface LegateData {
 	maxCenturionsPerUser: number;
 	maxTestudosPerUser: number;
 	maxWhitelistedMints: number;
+	maxPriceStalenessSeconds: number;
+	maxPriceConfidenceBps: number;
 	testudoTokenWhitelist: TokenWhitelistData[];
 	treasuryAcc: PublicKey;
 	percentForFees: number;
+	version: number; // Layout version, bumped by migrate_legate
+	feeTierMode: { bracket: {} } | { progressive: {} };
+	feeTiers: FeeTierData[];
+	minFeeLamports: number;
+	flatFeeLamports: number;
+	roundFeesUp: boolean;
+	operationFees: OperationFeesData;
+	approvedCpiPrograms: PublicKey[];
+	approvedSwapRouters: PublicKey[];
+	nftCollections: PublicKey[];
+	yieldPrograms: PublicKey[];
+}
+
+export interface DcaOrderData {
+	authority: PublicKey;
+	centurion: PublicKey;
+	orderId: number;
+	bump: number;
+	sourceMint: PublicKey;
+	destinationMint: PublicKey;
+	amountPerCycle: number;
+	minAmountOutPerCycle: number;
+	intervalSeconds: number;
+	totalCycles: number;
+	cyclesExecuted: number;
+	nextExecutionAt: number;
+	keeperTipLamports: number;
+	createdAt: number;
+	maxSlippageBps: number | null;
+}
+
+export interface ConditionalOrderData {
+	authority: PublicKey;
+	centurion: PublicKey;
+	orderId: number;
+	bump: number;
+	sourceMint: PublicKey;
+	destinationMint: PublicKey;
+	amountIn: number;
+	minAmountOut: number;
+	trigger: { priceBelow: {} } | { priceAbove: {} };
+	triggerPrice: number;
+	keeperTipLamports: number;
+	createdAt: number;
+	maxSlippageBps: number;
+}
+
+export interface RebalanceTargetData {
+	mint: PublicKey;
+	weightBps: number;
+}
+
+export interface RebalancePolicyData {
+	authority: PublicKey;
+	centurion: PublicKey;
+	bump: number;
+	targets: RebalanceTargetData[];
+	driftThresholdBps: number;
+	maxSlippageBps: number;
+	minIntervalSeconds: number;
+	lastRebalancedAt: number;
+	createdAt: number;
 }
 
 export interface TokenData {
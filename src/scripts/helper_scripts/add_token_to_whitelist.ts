@@ -46,6 +46,12 @@ async function main() {
                 tokenName: "USDC Devnet",
                 tokenSymbol: "USDC Dev",
                 tokenDecimals: 6,
+                percentForFees: null,
+                minFee: new anchor.BN(0),
+                flatFee: new anchor.BN(0),
+                priceFeed: null,
+                transferHookProgram: null,
+                riskFlags: 0,
             }
         )
 		.accountsPartial({
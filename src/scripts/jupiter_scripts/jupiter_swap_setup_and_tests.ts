@@ -184,6 +184,12 @@ console.log(`Centurion keypair: ${userKeypair.publicKey}`);
 			tokenName: "USDC",
 			tokenSymbol: "USDC",
 			tokenDecimals: 6,
+			percentForFees: null,
+			minFee: new BN(0),
+			flatFee: new BN(0),
+			priceFeed: null,
+			transferHookProgram: null,
+			riskFlags: 0,
 		})
 		.accounts({
 			authority: legateKeypair.publicKey,
@@ -328,15 +334,6 @@ console.log(`Centurion keypair: ${userKeypair.publicKey}`);
             ...convertToAccountMeta(cleanupInstruction.accounts)
         ];
         
-        const usdcAtaAddress = await getAssociatedTokenAddressSync(
-            new PublicKey(usdcAddress),
-            centurionPubkey,
-            true,
-        );
-        const usdcData = {
-            tokenMint: new PublicKey(usdcAddress),
-            testudoPubkey: usdcAtaAddress,
-        };
         const swapTx = await userProgram.methods
             .swap(
                 {
@@ -367,17 +364,32 @@ console.log(`Centurion keypair: ${userKeypair.publicKey}`);
                     ),
                     data: Buffer.from(cleanupInstruction.data, "base64"),
                 },
-                [usdcData]
+                new BN(quoteResponse.otherAmountThreshold),
+                new BN(quoteResponse.inAmount)
             )
             .accountsPartial({
                 authority: userKeypair.publicKey,
                 validSignerOfPassword: passwordKeypair.publicKey,
                 sourceMint: new PublicKey(wsolAddress),
                 destinationMint: new PublicKey(usdcAddress),
-                jupiterProgram: new PublicKey(
+                // SOL is swapped out of a temporary wSOL account
+                sourceTestudo: PublicKey.findProgramAddressSync(
+                    [
+                        centurionPubkey.toBuffer(),
+                        new PublicKey(wsolAddress).toBuffer(),
+                        Buffer.from("wsol"),
+                    ],
+                    userProgram.programId
+                )[0],
+                destinationTestudo: PublicKey.findProgramAddressSync(
+                    [centurionPubkey.toBuffer(), new PublicKey(usdcAddress).toBuffer()],
+                    userProgram.programId
+                )[0],
+                swapRouter: new PublicKey(
                     instructionsResponse.swapInstruction.programId
                 ),
-                tokenProgram: TOKEN_PROGRAM_ID,
+                sourceTokenProgram: TOKEN_PROGRAM_ID,
+                destinationTokenProgram: TOKEN_PROGRAM_ID,
                 treasury: treasuryKeypair.publicKey,
             })
             .remainingAccounts(remainingAccounts)